@@ -1,15 +1,51 @@
 use rand::Rng;
-use std::f64::consts::PI;
-use crate::{core::{colour::RGB, renderer::{Renderer, Point}}, utils::{self, triangle_polygon_collision}};
+use std::{f64::consts::PI, ops::Range};
+use crate::{core::{colour::RGB, renderer::Renderer}, geometry::{Polygon, Vec2}, utils::{self, triangle_polygon_collision}};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AsteroidSize {
+    Large,
+    Medium,
+    Small,
+}
+
+impl AsteroidSize {
+    pub fn child(self) -> Option<AsteroidSize> {
+        match self {
+            AsteroidSize::Large => Some(AsteroidSize::Medium),
+            AsteroidSize::Medium => Some(AsteroidSize::Small),
+            AsteroidSize::Small => None,
+        }
+    }
+
+    pub fn scale_range(self) -> Range<f64> {
+        match self {
+            AsteroidSize::Large => 38.0..50.0,
+            AsteroidSize::Medium => 20.0..27.0,
+            AsteroidSize::Small => 10.0..14.0,
+        }
+    }
+
+    pub fn speed_range(self) -> Range<f64> {
+        match self {
+            AsteroidSize::Large => 50.0..100.0,
+            AsteroidSize::Medium => 70.0..130.0,
+            AsteroidSize::Small => 100.0..170.0,
+        }
+    }
+}
 
 pub struct AsteroidConstructor {
     x: Option<f64>,
     y: Option<f64>,
-    vertices: Vec<Point>,
+    outline: Option<Polygon>,
     scale: Option<f64>,
     velocity_x: Option<f64>,
     velocity_y: Option<f64>,
-    parent: Option<bool>,
+    angle: Option<f64>,
+    angular_velocity: Option<f64>,
+    size: Option<AsteroidSize>,
+    speed_multiplier: Option<f64>,
     screen_width: u32,
     screen_height: u32,
 }
@@ -19,11 +55,14 @@ impl AsteroidConstructor {
         Self {
             x: None,
             y: None,
-            vertices: Vec::new(),
+            outline: None,
             scale: None,
             velocity_x: None,
             velocity_y: None,
-            parent: None,
+            angle: None,
+            angular_velocity: None,
+            size: None,
+            speed_multiplier: None,
             screen_width,
             screen_height,
         }
@@ -54,135 +93,211 @@ impl AsteroidConstructor {
         self
     }
 
-    pub fn parent(mut self, parent: bool) -> Self {
-        self.parent = Some(parent);
+    pub fn angle(mut self, angle: f64) -> Self {
+        self.angle = Some(angle);
         self
     }
 
-    pub fn build(self) -> Asteroid {
-        let mut rng = rand::thread_rng();
+    pub fn angular_velocity(mut self, angular_velocity: f64) -> Self {
+        self.angular_velocity = Some(angular_velocity);
+        self
+    }
 
-        let scale = self.scale.unwrap_or_else(|| rng.gen_range(30.0..50.0));
+    pub fn outline(mut self, outline: Polygon) -> Self {
+        self.outline = Some(outline);
+        self
+    }
+
+    pub fn size(mut self, size: AsteroidSize) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn speed_multiplier(mut self, speed_multiplier: f64) -> Self {
+        self.speed_multiplier = Some(speed_multiplier);
+        self
+    }
+
+    pub fn build(self, rng: &mut impl Rng) -> Asteroid {
+        let size = self.size.unwrap_or(AsteroidSize::Large);
+        let speed_multiplier = self.speed_multiplier.unwrap_or(1.0);
+        let scale = self.scale.unwrap_or_else(|| rng.gen_range(size.scale_range()));
         let (x, y) = self.x.zip(self.y).unwrap_or_else(|| {
-            utils::generate_spawn_points(self.screen_width, self.screen_height, scale)
+            utils::generate_spawn_points(rng, self.screen_width, self.screen_height, scale)
         });
         let (velocity_x, velocity_y) = self.velocity_x.zip(self.velocity_y).unwrap_or_else(|| {
-            utils::generate_velocity(0.01, 0.02)
+            let speed = size.speed_range();
+            utils::generate_velocity(rng, speed.start * speed_multiplier, speed.end * speed_multiplier)
+        });
+        let angle = self.angle.unwrap_or_else(|| rng.gen_range(0.0..2.0 * PI));
+        let angular_velocity = self.angular_velocity.unwrap_or_else(|| {
+            let sign = if rng.gen::<bool>() { 1.0 } else { -1.0 };
+            sign * rng.gen_range(0.2..1.5)
         });
-        let parent = self.parent.unwrap_or(false);
+        let outline = self.outline.unwrap_or_else(|| utils::generate_outline(rng, scale));
 
-        Asteroid {
+        let mut asteroid = Asteroid {
             x,
             y,
-            vertices: self.vertices,
+            prev_x: x,
+            prev_y: y,
+            angle,
+            prev_angle: angle,
+            angular_velocity,
+            outline,
+            vertices: Polygon::default(),
             scale,
             velocity_x,
             velocity_y,
-            parent,
-        }
+            size,
+            speed_multiplier,
+        };
+        asteroid.recalculate_vertices();
+        asteroid
     }
 }
 
 pub struct Asteroid {
-    pub parent: bool,
+    pub size: AsteroidSize,
     x: f64,
     y: f64,
-    vertices: Vec<Point>,
+    prev_x: f64,
+    prev_y: f64,
+    angle: f64,
+    prev_angle: f64,
+    angular_velocity: f64,
+    outline: Polygon,
+    vertices: Polygon,
     scale: f64,
     velocity_x: f64,
     velocity_y: f64,
+    speed_multiplier: f64,
 }
 
 impl Asteroid {
-    pub fn update(&mut self, screen_width: u32, screen_height: u32) {
-        self.move_asteroid();
+    pub fn update(&mut self, dt: f64, screen_width: u32, screen_height: u32) {
+        self.move_asteroid(dt);
         self.ensure_asteroid_is_on_screen(screen_width, screen_height);
         self.recalculate_vertices();
     }
 
-    pub fn draw(&self, renderer: &mut impl Renderer, colour: RGB) -> Result<(), String> {
-        renderer.draw_lines(&self.vertices[..], colour)?;
+    pub fn draw(&self, renderer: &mut impl Renderer, colour: RGB, alpha: f64) -> Result<(), String> {
+        let x = utils::lerp(self.prev_x, self.x, alpha);
+        let y = utils::lerp(self.prev_y, self.y, alpha);
+        let angle = utils::lerp(self.prev_angle, self.angle, alpha);
+        renderer.draw_lines(&self.vertices_at(x, y, angle).to_closed_points(), colour)?;
         Ok(())
     }
 
-    pub fn is_hit(&self, laser_x: f64, laser_y: f64) -> bool {
-        let laser_point = Point::new(laser_x as i32, laser_y as i32);
-        utils::is_point_in_polygon(laser_point, &self.vertices)
+    pub fn is_hit(&self, start: Vec2, end: Vec2) -> bool {
+        utils::segment_polygon_collision(start, end, &self.vertices)
     }
 
-    pub fn is_colliding(&self, triangle: &Vec<Point>) -> bool {
-        triangle_polygon_collision(&triangle, &self.vertices)
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        self.vertices.bounds()
     }
 
-    pub fn generate_child(&self, screen_width: u32, screen_height: u32) -> Asteroid {
-        let (x, y) = self.generate_child_position();
-        let (velocity_x, velocity_y) = self.generate_child_velocity();
-        let scale = self.generate_child_scale();
+    pub fn position(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
 
-        AsteroidConstructor::new(screen_width, screen_height)
+    pub fn bounce_off(&mut self, centre: Vec2, radius: f64) -> bool {
+        if !utils::circle_polygon_collision(centre, radius, &self.vertices) {
+            return false;
+        }
+        let normal = Vec2::new(self.x, self.y) - centre;
+        let length = normal.length();
+        if length == 0.0 {
+            return false;
+        }
+        let normal = normal * (1.0 / length);
+        let velocity = Vec2::new(self.velocity_x, self.velocity_y);
+        let approach = velocity.dot(normal);
+        if approach >= 0.0 {
+            return false;
+        }
+        let velocity = velocity - normal * (2.0 * approach);
+        self.velocity_x = velocity.x;
+        self.velocity_y = velocity.y;
+        true
+    }
+
+    pub fn is_colliding(&self, triangle: &Polygon) -> bool {
+        triangle_polygon_collision(triangle, &self.vertices)
+    }
+
+    pub fn generate_child(&self, rng: &mut impl Rng, screen_width: u32, screen_height: u32) -> Option<Asteroid> {
+        let size = self.size.child()?;
+        let (x, y) = self.generate_child_position(rng);
+        let (velocity_x, velocity_y) = self.generate_child_velocity(rng, size);
+        let angular_velocity = self.generate_child_angular_velocity(rng);
+
+        let child = AsteroidConstructor::new(screen_width, screen_height)
         .x(x)
         .y(y)
         .velocity_x(velocity_x)
         .velocity_y(velocity_y)
-        .scale(scale)
-        .build()
+        .angle(self.angle)
+        .angular_velocity(angular_velocity)
+        .size(size)
+        .speed_multiplier(self.speed_multiplier)
+        .build(rng);
+        Some(child)
     }
 
-    fn generate_child_position(&self) -> (f64, f64) {
-        let mut rng = rand::thread_rng();
+    fn generate_child_position(&self, rng: &mut impl Rng) -> (f64, f64) {
         let variance = 20.0;
         let delta_x = rng.gen_range(-variance..variance);
         let delta_y = rng.gen_range(-variance..variance);
         (self.x + delta_x, self.y + delta_y)
     }
 
-    fn generate_child_velocity(&self) -> (f64, f64) {
-        let mut rng = rand::thread_rng();
-        let x_variance = 0.25*self.velocity_x.abs();
-        let y_variance = 0.25*self.velocity_y.abs();
-        let delta_x = rng.gen_range(-x_variance..x_variance);
-        let delta_y = rng.gen_range(-y_variance..y_variance);
-        (self.velocity_x + delta_x, self.velocity_y + delta_y)
+    fn generate_child_velocity(&self, rng: &mut impl Rng, size: AsteroidSize) -> (f64, f64) {
+        let heading = self.velocity_y.atan2(self.velocity_x) + rng.gen_range(-0.8..0.8);
+        let speed = rng.gen_range(size.speed_range()) * self.speed_multiplier;
+        let velocity = Vec2::from_angle(heading, speed);
+        (velocity.x, velocity.y)
     }
 
-    fn generate_child_scale(&self) -> f64 {
-        let mut rng = rand::thread_rng();
-        0.4*self.scale + rng.gen_range(-0.2*self.scale..0.2*self.scale)
+    fn generate_child_angular_velocity(&self, rng: &mut impl Rng) -> f64 {
+        self.angular_velocity + rng.gen_range(-1.0..1.0)
     }
 
-    fn move_asteroid(&mut self) {
-        self.x += self.velocity_x;
-        self.y += self.velocity_y;
+    fn move_asteroid(&mut self, dt: f64) {
+        self.prev_x = self.x;
+        self.prev_y = self.y;
+        self.prev_angle = self.angle;
+        self.angle += self.angular_velocity * dt;
+        self.x += self.velocity_x * dt;
+        self.y += self.velocity_y * dt;
     }
 
     fn recalculate_vertices(&mut self) {
-        self.vertices.clear();
-        let num_points = 7;
-
-        for i in 0..num_points {
-            let angle = 2.0 * PI * i as f64 / num_points as f64;
-            let x_offset = self.scale * angle.cos();
-            let y_offset = self.scale * angle.sin();
-            self.vertices.push(Point::new((self.x + x_offset) as i32, (self.y + y_offset) as i32));
-        }
+        self.vertices = self.vertices_at(self.x, self.y, self.angle);
+    }
 
-        self.vertices.push(self.vertices[0]);
+    fn vertices_at(&self, x: f64, y: f64, angle: f64) -> Polygon {
+        self.outline.transformed(angle, Vec2::new(x, y))
     }
 
     fn ensure_asteroid_is_on_screen(&mut self, screen_width: u32, screen_height: u32) {
         if self.is_off_screen_x(screen_width) {
-            if self.velocity_x > 0.0 {
-                self.x = -self.scale;
+            let x = if self.velocity_x > 0.0 {
+                -self.scale
             } else {
-                self.x = self.scale + screen_width as f64;
-            }
+                self.scale + screen_width as f64
+            };
+            self.prev_x += x - self.x;
+            self.x = x;
         }
         if self.is_off_screen_y(screen_height) {
-            if self.velocity_y > 0.0 {
-                self.y = -self.scale;
+            let y = if self.velocity_y > 0.0 {
+                -self.scale
             } else {
-                self.y = self.scale + screen_height as f64;
-            }
+                self.scale + screen_height as f64
+            };
+            self.prev_y += y - self.y;
+            self.y = y;
         }
     }
 
@@ -195,3 +310,82 @@ impl Asteroid {
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn bar() -> Polygon {
+        Polygon::new(vec![
+            Vec2::new(-50.0, -5.0),
+            Vec2::new(50.0, -5.0),
+            Vec2::new(50.0, 5.0),
+            Vec2::new(-50.0, 5.0),
+        ])
+    }
+
+    fn spinning_bar(angular_velocity: f64) -> Asteroid {
+        AsteroidConstructor::new(800, 600)
+            .x(400.0)
+            .y(300.0)
+            .velocity_x(0.0)
+            .velocity_y(0.0)
+            .scale(50.0)
+            .angle(0.0)
+            .angular_velocity(angular_velocity)
+            .outline(bar())
+            .build(&mut StdRng::seed_from_u64(0))
+    }
+
+    fn contains(asteroid: &Asteroid, x: f64, y: f64) -> bool {
+        asteroid.is_hit(Vec2::new(x, y), Vec2::new(x, y))
+    }
+
+    #[test]
+    fn rotation_is_applied_to_collision_vertices() {
+        let mut asteroid = spinning_bar(PI / 2.0);
+        assert!(contains(&asteroid, 440.0, 300.0));
+        assert!(!contains(&asteroid, 400.0, 340.0));
+
+        asteroid.update(1.0, 800, 600);
+
+        assert!(!contains(&asteroid, 440.0, 300.0));
+        assert!(contains(&asteroid, 400.0, 340.0));
+    }
+
+    #[test]
+    fn children_inherit_and_perturb_spin() {
+        let parent = spinning_bar(1.0);
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..20 {
+            let child = parent.generate_child(&mut rng, 800, 600).unwrap();
+            assert_eq!(child.angle, parent.angle);
+            assert!((child.angular_velocity - parent.angular_velocity).abs() <= 1.0);
+        }
+    }
+
+    #[test]
+    fn sizes_split_large_to_medium_to_small() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let large = AsteroidConstructor::new(800, 600).size(AsteroidSize::Large).build(&mut rng);
+        let medium = large.generate_child(&mut rng, 800, 600).unwrap();
+        let small = medium.generate_child(&mut rng, 800, 600).unwrap();
+
+        assert_eq!(medium.size, AsteroidSize::Medium);
+        assert_eq!(small.size, AsteroidSize::Small);
+        assert!(small.generate_child(&mut rng, 800, 600).is_none());
+        assert!(large.scale > medium.scale && medium.scale > small.scale);
+    }
+
+    #[test]
+    fn children_move_within_their_speed_range() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let large = AsteroidConstructor::new(800, 600).size(AsteroidSize::Large).build(&mut rng);
+        for _ in 0..20 {
+            let child = large.generate_child(&mut rng, 800, 600).unwrap();
+            let speed = Vec2::new(child.velocity_x, child.velocity_y).length();
+            assert!(AsteroidSize::Medium.speed_range().contains(&speed));
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use asteroids::core::{
+    colour::RGB,
+    framebuffer::FramebufferRenderer,
+    input::{DemoController, InputController, ScriptedController},
+    renderer::{NullRenderer, Renderer},
+    replay::{self, Recording, ReplayController},
+};
+use asteroids::game_state::{GameState, State, TIMESTEP};
+
+const DEFAULT_TICKS: u64 = 120 * 60;
+const REPORT_INTERVAL: u64 = 120;
+
+const SCREEN_WIDTH: u32 = 800;
+const SCREEN_HEIGHT: u32 = 600;
+
+struct Args {
+    ticks: u64,
+    seed: Option<u64>,
+    record: Option<String>,
+    replay: Option<String>,
+    expect_score: Option<u32>,
+    screenshot: Option<String>,
+}
+
+fn main() -> Result<(), String> {
+    let args = parse_args()?;
+    let game_state = match &args.replay {
+        Some(path) => replay(path, args.expect_score)?,
+        None => soak(&args)?,
+    };
+    if let Some(path) = &args.screenshot {
+        screenshot(&game_state, path)?;
+    }
+    Ok(())
+}
+
+fn soak(args: &Args) -> Result<GameState, String> {
+    let seed = args.seed.unwrap_or_else(rand::random);
+
+    let mut renderer = NullRenderer;
+    let mut game_state = GameState::new(SCREEN_WIDTH, SCREEN_HEIGHT, seed);
+    let mut recording = Recording::new(seed);
+    let mut games_played = 1;
+    println!("seed: {}", seed);
+
+    for tick in 0..args.ticks {
+        let commands = DemoController::new(tick).poll();
+        recording.record(&commands);
+        game_state.update(&ScriptedController::new(commands), TIMESTEP.as_secs_f64());
+        game_state.draw(&mut renderer, 1.0)?;
+
+        if tick % REPORT_INTERVAL == 0 {
+            report(tick, &game_state);
+        }
+
+        if matches!(game_state.state, State::EnterInitials | State::GameOver) {
+            println!(
+                "game over at tick {} with score {} (seed {})",
+                tick, game_state.player.score, game_state.seed,
+            );
+            recording.final_score = Some(game_state.player.score);
+            save_recording(args, &recording, games_played)?;
+            game_state.reset(game_state.seed.wrapping_add(1));
+            game_state.state = State::Playing;
+            recording = Recording::new(game_state.seed);
+            games_played += 1;
+        }
+    }
+
+    if !recording.is_empty() {
+        recording.final_score = Some(game_state.player.score);
+        save_recording(args, &recording, games_played)?;
+    }
+
+    report(args.ticks, &game_state);
+    println!("games played: {}", games_played);
+    Ok(game_state)
+}
+
+fn replay(path: &str, expect_score: Option<u32>) -> Result<GameState, String> {
+    let mut controller = ReplayController::new(Recording::load(path)?);
+    let expected = expect_score.or(controller.expected_score());
+
+    let mut renderer = NullRenderer;
+    let mut game_state = GameState::new(SCREEN_WIDTH, SCREEN_HEIGHT, controller.seed());
+    let mut tick = 0;
+    println!("seed: {}", controller.seed());
+
+    while !controller.is_finished() && game_state.state == State::Playing {
+        game_state.update(&controller, TIMESTEP.as_secs_f64());
+        game_state.draw(&mut renderer, 1.0)?;
+        controller.advance();
+
+        if tick % REPORT_INTERVAL == 0 {
+            report(tick, &game_state);
+        }
+        tick += 1;
+    }
+
+    report(tick, &game_state);
+    println!("final score: {}", game_state.player.score);
+    match expected {
+        Some(score) if score != game_state.player.score => Err(format!(
+            "replay diverged: expected final score {}, got {}",
+            score, game_state.player.score,
+        )),
+        _ => Ok(game_state),
+    }
+}
+
+fn screenshot(game_state: &GameState, path: &str) -> Result<(), String> {
+    let mut renderer = FramebufferRenderer::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+    renderer.set_colour(RGB::BLACK);
+    renderer.clear();
+    game_state.draw(&mut renderer, 1.0)?;
+    renderer.save_ppm(path)
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut parsed = Args { ticks: DEFAULT_TICKS, seed: None, record: None, replay: None, expect_score: None, screenshot: None };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let value = args.next().ok_or("--seed requires a value")?;
+                parsed.seed = Some(value.parse::<u64>().map_err(|e| format!("invalid seed '{}': {}", value, e))?);
+            }
+            "--record" => {
+                parsed.record = Some(args.next().ok_or("--record requires a path")?);
+            }
+            "--replay" => {
+                parsed.replay = Some(args.next().ok_or("--replay requires a path")?);
+            }
+            "--expect-score" => {
+                let value = args.next().ok_or("--expect-score requires a value")?;
+                parsed.expect_score = Some(value.parse::<u32>().map_err(|e| format!("invalid score '{}': {}", value, e))?);
+            }
+            "--screenshot" => {
+                parsed.screenshot = Some(args.next().ok_or("--screenshot requires a path")?);
+            }
+            _ => {
+                parsed.ticks = arg.parse::<u64>().map_err(|e| format!("invalid tick count '{}': {}", arg, e))?;
+            }
+        }
+    }
+    Ok(parsed)
+}
+
+fn save_recording(args: &Args, recording: &Recording, game: usize) -> Result<(), String> {
+    match &args.record {
+        Some(path) => recording.save(replay::numbered_path(path, game)),
+        None => Ok(()),
+    }
+}
+
+fn report(tick: u64, game_state: &GameState) {
+    println!(
+        "tick {:>6}  wave {:>2}  score {:>4}  lives {}  asteroids {:>3}",
+        tick,
+        game_state.wave,
+        game_state.player.score,
+        game_state.player.lives(),
+        game_state.asteroids.len(),
+    );
+}
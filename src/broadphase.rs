@@ -0,0 +1,122 @@
+use crate::geometry::Vec2;
+
+pub struct SpatialHash {
+    cell_size: f64,
+    columns: usize,
+    rows: usize,
+    cells: Vec<Vec<usize>>,
+}
+
+impl SpatialHash {
+    pub fn new(screen_width: u32, screen_height: u32, cell_size: f64) -> Self {
+        let columns = (screen_width as f64 / cell_size).ceil().max(1.0) as usize;
+        let rows = (screen_height as f64 / cell_size).ceil().max(1.0) as usize;
+        SpatialHash {
+            cell_size,
+            columns,
+            rows,
+            cells: vec![Vec::new(); columns * rows],
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for cell in self.cells.iter_mut() {
+            cell.clear();
+        }
+    }
+
+    pub fn insert(&mut self, index: usize, min: Vec2, max: Vec2) {
+        let covered: Vec<usize> = self.covered_cells(min, max).collect();
+        for cell in covered {
+            self.cells[cell].push(index);
+        }
+    }
+
+    pub fn query(&self, min: Vec2, max: Vec2) -> Vec<usize> {
+        let mut found: Vec<usize> = self
+            .covered_cells(min, max)
+            .flat_map(|cell| self.cells[cell].iter().copied())
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    fn covered_cells(&self, min: Vec2, max: Vec2) -> impl Iterator<Item = usize> + '_ {
+        let (first_column, column_span) = self.span(min.x, max.x, self.columns);
+        let (first_row, row_span) = self.span(min.y, max.y, self.rows);
+
+        (0..row_span).flat_map(move |r| {
+            let row = (first_row + r as i64).rem_euclid(self.rows as i64) as usize;
+            (0..column_span).map(move |c| {
+                let column = (first_column + c as i64).rem_euclid(self.columns as i64) as usize;
+                row * self.columns + column
+            })
+        })
+    }
+
+    fn span(&self, min: f64, max: f64, count: usize) -> (i64, usize) {
+        if min > max || !min.is_finite() || !max.is_finite() {
+            return (0, 0);
+        }
+        let first = (min / self.cell_size).floor() as i64;
+        let last = (max / self.cell_size).floor() as i64;
+        let span = (last - first + 1).clamp(1, count as i64) as usize;
+        (first, span)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f64, y: f64) -> Vec2 {
+        Vec2::new(x, y)
+    }
+
+    #[test]
+    fn query_finds_overlapping_entries() {
+        let mut hash = SpatialHash::new(800, 600, 100.0);
+        hash.insert(0, v(10.0, 10.0), v(50.0, 50.0));
+        hash.insert(1, v(400.0, 300.0), v(450.0, 350.0));
+
+        assert_eq!(hash.query(v(40.0, 40.0), v(60.0, 60.0)), vec![0]);
+        assert_eq!(hash.query(v(420.0, 320.0), v(420.0, 320.0)), vec![1]);
+        assert!(hash.query(v(700.0, 500.0), v(750.0, 550.0)).is_empty());
+    }
+
+    #[test]
+    fn entries_spanning_cells_are_reported_once() {
+        let mut hash = SpatialHash::new(800, 600, 100.0);
+        hash.insert(3, v(50.0, 50.0), v(350.0, 250.0));
+
+        assert_eq!(hash.query(v(0.0, 0.0), v(799.0, 599.0)), vec![3]);
+    }
+
+    #[test]
+    fn entries_off_the_edge_wrap_around() {
+        let mut hash = SpatialHash::new(800, 600, 100.0);
+        hash.insert(0, v(-40.0, 300.0), v(20.0, 340.0));
+        hash.insert(1, v(300.0, 590.0), v(340.0, 640.0));
+
+        assert_eq!(hash.query(v(780.0, 310.0), v(790.0, 320.0)), vec![0]);
+        assert_eq!(hash.query(v(310.0, 10.0), v(320.0, 20.0)), vec![1]);
+    }
+
+    #[test]
+    fn empty_bounds_cover_no_cells() {
+        let mut hash = SpatialHash::new(800, 600, 100.0);
+        hash.insert(0, v(f64::INFINITY, f64::INFINITY), v(f64::NEG_INFINITY, f64::NEG_INFINITY));
+
+        assert!(hash.query(v(0.0, 0.0), v(799.0, 599.0)).is_empty());
+    }
+
+    #[test]
+    fn clear_removes_all_entries() {
+        let mut hash = SpatialHash::new(800, 600, 100.0);
+        hash.insert(0, v(10.0, 10.0), v(50.0, 50.0));
+        hash.clear();
+
+        assert!(hash.query(v(0.0, 0.0), v(799.0, 599.0)).is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct RGB {
     pub r: u8,
@@ -8,8 +9,10 @@ pub struct RGB {
 impl RGB {
     pub const WHITE: Self = Self { r: 255, g: 255, b: 255 };
     pub const BLACK: Self = Self { r: 0, g: 0, b: 0 };
+    pub const RED: Self = Self { r: 255, g: 80, b: 80 };
 }
 
+#[cfg(feature = "sdl")]
 impl From<RGB> for sdl2::pixels::Color {
     fn from(c: RGB) -> Self {
         sdl2::pixels::Color::RGB(c.r, c.g, c.b)
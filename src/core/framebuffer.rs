@@ -0,0 +1,318 @@
+use std::{fs, path::Path};
+
+use super::{colour::RGB, renderer::{Point, Renderer}};
+use crate::utils;
+
+const GLYPH_WIDTH: i32 = 5;
+const GLYPH_HEIGHT: i32 = 7;
+const GLYPH_SCALE: i32 = 4;
+const GLYPH_ADVANCE: i32 = (GLYPH_WIDTH + 1) * GLYPH_SCALE;
+
+pub struct FramebufferRenderer {
+    width: u32,
+    height: u32,
+    colour: RGB,
+    pixels: Vec<RGB>,
+}
+
+impl FramebufferRenderer {
+    pub fn new(width: u32, height: u32) -> Self {
+        FramebufferRenderer {
+            width,
+            height,
+            colour: RGB::BLACK,
+            pixels: vec![RGB::BLACK; (width * height) as usize],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[RGB] {
+        &self.pixels
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> Option<RGB> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[(y * self.width + x) as usize])
+        } else {
+            None
+        }
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for pixel in &self.pixels {
+            bytes.extend_from_slice(&[pixel.r, pixel.g, pixel.b]);
+        }
+        bytes
+    }
+
+    pub fn save_ppm(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        fs::write(path, self.to_ppm()).map_err(|e| e.to_string())
+    }
+
+    fn put_pixel(&mut self, x: i32, y: i32, colour: RGB) {
+        if x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height {
+            self.pixels[(y as u32 * self.width + x as u32) as usize] = colour;
+        }
+    }
+
+    fn draw_line(&mut self, start: Point, end: Point, colour: RGB) {
+        let dx = (end.x - start.x).abs();
+        let dy = -(end.y - start.y).abs();
+        let step_x = if start.x < end.x { 1 } else { -1 };
+        let step_y = if start.y < end.y { 1 } else { -1 };
+        let mut error = dx + dy;
+        let (mut x, mut y) = (start.x, start.y);
+
+        loop {
+            self.put_pixel(x, y, colour);
+            if x == end.x && y == end.y {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    fn draw_glyph(&mut self, c: char, colour: RGB, x: i32, y: i32) {
+        let rows = glyph(c.to_ascii_uppercase());
+        for (row, bits) in rows.iter().enumerate() {
+            for column in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - column)) != 0 {
+                    self.draw_rect(
+                        x + column * GLYPH_SCALE,
+                        y + row as i32 * GLYPH_SCALE,
+                        GLYPH_SCALE as u32,
+                        GLYPH_SCALE as u32,
+                        colour,
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl Renderer for FramebufferRenderer {
+    fn set_colour(&mut self, colour: RGB) {
+        self.colour = colour;
+    }
+
+    fn clear(&mut self) {
+        self.pixels.fill(self.colour);
+    }
+
+    fn draw_rect(&mut self, x: i32, y: i32, w: u32, h: u32, colour: RGB) {
+        for py in y..y + h as i32 {
+            for px in x..x + w as i32 {
+                self.put_pixel(px, py, colour);
+            }
+        }
+    }
+
+    fn draw_lines(&mut self, vertices: &[Point], colour: RGB) -> Result<(), String> {
+        for pair in vertices.windows(2) {
+            self.draw_line(pair[0], pair[1], colour);
+        }
+        Ok(())
+    }
+
+    fn draw_text(&mut self, text: &str, colour: RGB, position: (i32, i32)) -> Result<(), String> {
+        let (x, y) = position;
+        for (i, c) in text.chars().enumerate() {
+            self.draw_glyph(c, colour, x + i as i32 * GLYPH_ADVANCE, y);
+        }
+        Ok(())
+    }
+
+    fn draw_vertices(&mut self, vertices: &[Point], colour: RGB) -> Result<(), String> {
+        let mut sorted_vertices = vertices.to_vec();
+        sorted_vertices.sort_by_key(|point| point.y);
+
+        let (x1, y1) = (sorted_vertices[0].x, sorted_vertices[0].y);
+        let (x2, y2) = (sorted_vertices[1].x, sorted_vertices[1].y);
+        let (x3, y3) = (sorted_vertices[2].x, sorted_vertices[2].y);
+
+        for y in y1..=y3 {
+            let x_start = if y < y2 {
+                utils::interpolate(y, y1, y2, x1, x2)
+            } else {
+                utils::interpolate(y, y2, y3, x2, x3)
+            };
+
+            let x_end = utils::interpolate(y, y1, y3, x1, x3);
+
+            for x in x_start.min(x_end)..=x_start.max(x_end) {
+                self.put_pixel(x, y, colour);
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_game_over_screen(&mut self, score: u32, seed: u64) -> Result<(), String> {
+        let colour = RGB::WHITE;
+        let centre_x = (self.width / 2) as i32;
+        let centre_y = (self.height / 2) as i32;
+
+        self.draw_text("GAME OVER", colour, (centre_x - 110, centre_y - 100))?;
+        self.draw_text(&format!("SCORE: {}", score), colour, (centre_x - 100, centre_y - 50))?;
+        self.draw_text("Press Enter to play again", colour, (centre_x - 250, centre_y))?;
+        self.draw_text(&format!("SEED: {}", seed), colour, (centre_x - 250, centre_y + 100))?;
+        Ok(())
+    }
+
+    fn present(&mut self) {}
+}
+
+fn glyph(c: char) -> [u8; GLYPH_HEIGHT as usize] {
+    match c {
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        ':' => [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000],
+        '.' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100],
+        '-' => [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000],
+        '_' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b11111],
+        '/' => [0b00001, 0b00010, 0b00010, 0b00100, 0b01000, 0b01000, 0b10000],
+        '!' => [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100],
+        '>' => [0b01000, 0b00100, 0b00010, 0b00001, 0b00010, 0b00100, 0b01000],
+        '<' => [0b00010, 0b00100, 0b01000, 0b10000, 0b01000, 0b00100, 0b00010],
+        ' ' => [0; GLYPH_HEIGHT as usize],
+        _ => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: RGB = RGB { r: 255, g: 0, b: 0 };
+
+    fn lit(renderer: &FramebufferRenderer) -> Vec<(u32, u32)> {
+        let mut points = Vec::new();
+        for y in 0..renderer.height() {
+            for x in 0..renderer.width() {
+                if renderer.pixel(x, y) != Some(RGB::BLACK) {
+                    points.push((x, y));
+                }
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn lines_light_every_pixel_between_endpoints() {
+        let mut renderer = FramebufferRenderer::new(8, 8);
+        renderer.draw_lines(&[Point::new(1, 1), Point::new(5, 1), Point::new(5, 3)], RED).unwrap();
+
+        assert_eq!(lit(&renderer), vec![(1, 1), (2, 1), (3, 1), (4, 1), (5, 1), (5, 2), (5, 3)]);
+        assert_eq!(renderer.pixel(5, 2), Some(RED));
+    }
+
+    #[test]
+    fn diagonal_line_steps_one_pixel_at_a_time() {
+        let mut renderer = FramebufferRenderer::new(8, 8);
+        renderer.draw_lines(&[Point::new(6, 0), Point::new(0, 6)], RED).unwrap();
+
+        assert_eq!(lit(&renderer), (0..=6).map(|i| (6 - i, i)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn drawing_is_clipped_to_the_buffer() {
+        let mut renderer = FramebufferRenderer::new(4, 4);
+        renderer.draw_rect(-2, 2, 4, 10, RED);
+        renderer.draw_lines(&[Point::new(-5, 0), Point::new(10, 0)], RED).unwrap();
+
+        assert_eq!(lit(&renderer), vec![(0, 0), (1, 0), (2, 0), (3, 0), (0, 2), (1, 2), (0, 3), (1, 3)]);
+    }
+
+    #[test]
+    fn text_renders_scaled_glyphs() {
+        let mut renderer = FramebufferRenderer::new(64, 32);
+        renderer.draw_text("I-", RGB::WHITE, (2, 1)).unwrap();
+
+        // The top bar of 'I' spans columns 1..4 of the glyph, each 4 px wide.
+        assert_eq!(renderer.pixel(2 + 4, 1), Some(RGB::WHITE));
+        assert_eq!(renderer.pixel(2 + 15, 1 + 3), Some(RGB::WHITE));
+        assert_eq!(renderer.pixel(2 + 3, 1), Some(RGB::BLACK));
+        assert_eq!(renderer.pixel(2 + 16, 1), Some(RGB::BLACK));
+        // '-' is advanced by a full cell and only fills its middle row.
+        let dash_x = 2 + GLYPH_ADVANCE as u32;
+        assert_eq!(renderer.pixel(dash_x, 1 + 12), Some(RGB::WHITE));
+        assert_eq!(renderer.pixel(dash_x, 1 + 11), Some(RGB::BLACK));
+        assert_eq!(lit(&renderer).len(), (11 + 5) * 16);
+    }
+
+    #[test]
+    fn vertices_fill_a_triangle() {
+        let mut renderer = FramebufferRenderer::new(8, 8);
+        renderer.draw_vertices(&[Point::new(0, 0), Point::new(4, 0), Point::new(0, 4)], RED).unwrap();
+
+        assert_eq!(renderer.pixel(1, 1), Some(RED));
+        assert_eq!(renderer.pixel(0, 4), Some(RED));
+        assert_eq!(renderer.pixel(4, 4), Some(RGB::BLACK));
+    }
+
+    #[test]
+    fn ppm_has_header_and_rgb_triples() {
+        let mut renderer = FramebufferRenderer::new(2, 1);
+        renderer.set_colour(RED);
+        renderer.clear();
+        renderer.draw_rect(1, 0, 1, 1, RGB::WHITE);
+
+        let ppm = renderer.to_ppm();
+
+        assert_eq!(ppm, [b"P6\n2 1\n255\n".as_slice(), &[255, 0, 0, 255, 255, 255]].concat());
+
+        let path = std::env::temp_dir().join(format!("asteroids-framebuffer-{}.ppm", std::process::id()));
+        renderer.save_ppm(&path).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), ppm);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,25 +1,90 @@
-#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Command {
     RotateLeft,
     RotateRight,
     Accelerate,
     Fire,
+    Hyperspace,
+    Shield,
+}
+
+impl Command {
+    pub const ALL: [Command; 6] = [
+        Command::RotateLeft,
+        Command::RotateRight,
+        Command::Accelerate,
+        Command::Fire,
+        Command::Hyperspace,
+        Command::Shield,
+    ];
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuCommand {
+    Up,
+    Down,
+    Left,
+    Right,
+    Select,
+    Back,
 }
 
 pub trait InputController {
     fn poll(&self) -> Vec<Command>;
 }
 
+pub struct ScriptedController {
+    commands: Vec<Command>,
+}
+
+impl ScriptedController {
+    pub fn new(commands: Vec<Command>) -> Self {
+        ScriptedController { commands }
+    }
+}
+
+impl InputController for ScriptedController {
+    fn poll(&self) -> Vec<Command> {
+        self.commands.clone()
+    }
+}
+
+pub struct DemoController {
+    tick: u64,
+}
+
+impl DemoController {
+    pub fn new(tick: u64) -> Self {
+        DemoController { tick }
+    }
+}
+
+impl InputController for DemoController {
+    fn poll(&self) -> Vec<Command> {
+        let mut commands = vec![Command::Fire];
+        match self.tick / 60 % 4 {
+            0 => commands.push(Command::RotateLeft),
+            1 => commands.push(Command::Accelerate),
+            2 => commands.push(Command::RotateRight),
+            _ => {}
+        }
+        commands
+    }
+}
+
+#[cfg(feature = "sdl")]
 pub struct SdlController<'a> {
     pump: &'a sdl2::EventPump,
 }
 
+#[cfg(feature = "sdl")]
 impl<'a> SdlController<'a> {
     pub fn new(controller: &'a sdl2::EventPump) -> Self {
-        return SdlController { pump: controller };
+        SdlController { pump: controller }
     }
 }
 
+#[cfg(feature = "sdl")]
 impl<'a> InputController for SdlController<'a> {
     fn poll(&self) -> Vec<Command> {
         let mut res: Vec<Command> = Vec::new();
@@ -43,7 +108,44 @@ impl<'a> InputController for SdlController<'a> {
         if keyboard.is_scancode_pressed(sdl2::keyboard::Scancode::Space) {
             res.push(Command::Fire)
         }
+        if keyboard.is_scancode_pressed(sdl2::keyboard::Scancode::LShift)
+            || keyboard.is_scancode_pressed(sdl2::keyboard::Scancode::RShift)
+        {
+            res.push(Command::Hyperspace)
+        }
+        if keyboard.is_scancode_pressed(sdl2::keyboard::Scancode::Down)
+            || keyboard.is_scancode_pressed(sdl2::keyboard::Scancode::S)
+        {
+            res.push(Command::Shield)
+        }
 
         res
     }
 }
+
+#[cfg(feature = "sdl")]
+pub fn menu_command(event: &sdl2::event::Event) -> Option<MenuCommand> {
+    use sdl2::{controller::Button, event::Event, keyboard::Keycode};
+
+    match event {
+        Event::KeyDown { keycode: Some(keycode), repeat: false, .. } => match *keycode {
+            Keycode::Up | Keycode::W => Some(MenuCommand::Up),
+            Keycode::Down | Keycode::S => Some(MenuCommand::Down),
+            Keycode::Left | Keycode::A => Some(MenuCommand::Left),
+            Keycode::Right | Keycode::D => Some(MenuCommand::Right),
+            Keycode::Return | Keycode::Space => Some(MenuCommand::Select),
+            Keycode::Escape | Keycode::Backspace => Some(MenuCommand::Back),
+            _ => None,
+        },
+        Event::ControllerButtonDown { button, .. } => match button {
+            Button::DPadUp => Some(MenuCommand::Up),
+            Button::DPadDown => Some(MenuCommand::Down),
+            Button::DPadLeft => Some(MenuCommand::Left),
+            Button::DPadRight => Some(MenuCommand::Right),
+            Button::A | Button::Start => Some(MenuCommand::Select),
+            Button::B | Button::Back => Some(MenuCommand::Back),
+            _ => None,
+        },
+        _ => None,
+    }
+}
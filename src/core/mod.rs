@@ -1,3 +1,5 @@
 pub mod input;
 pub mod colour;
 pub mod renderer;
+pub mod framebuffer;
+pub mod replay;
@@ -1,6 +1,7 @@
-use crate::utils;
-
 use super::colour::RGB;
+#[cfg(feature = "sdl")]
+use crate::utils;
+#[cfg(feature = "sdl")]
 use sdl2::{
     rect::Rect,
     render::Canvas,
@@ -13,6 +14,7 @@ pub struct Point {
     pub y: i32,
 }
 
+#[cfg(feature = "sdl")]
 impl From<Point> for sdl2::rect::Point {
     fn from(p: Point) -> Self {
         sdl2::rect::Point::new(p.x, p.y)
@@ -32,11 +34,54 @@ pub trait Renderer {
     // fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32);
     fn draw_lines(&mut self, vertices: &[Point], colour: RGB) -> Result<(), String>;
     fn draw_text(&mut self, text: &str, colour: RGB, position: (i32, i32)) -> Result<(), String>;
-    fn draw_vertices(&mut self, vertices: &Vec<Point>, colour: RGB) -> Result<(), String>;
-    fn draw_game_over_screen(&mut self, score: u32) -> Result<(), String>;
+    fn draw_vertices(&mut self, vertices: &[Point], colour: RGB) -> Result<(), String>;
+    fn draw_game_over_screen(&mut self, score: u32, seed: u64) -> Result<(), String>;
     fn present(&mut self);
+
+    fn draw_circle(&mut self, centre: Point, radius: i32, colour: RGB) -> Result<(), String> {
+        let segments = 24;
+        let points: Vec<Point> = (0..=segments)
+            .map(|i| {
+                let angle = i as f64 * std::f64::consts::TAU / segments as f64;
+                Point::new(
+                    centre.x + (radius as f64 * angle.cos()).round() as i32,
+                    centre.y + (radius as f64 * angle.sin()).round() as i32,
+                )
+            })
+            .collect();
+        self.draw_lines(&points, colour)
+    }
 }
 
+pub struct NullRenderer;
+
+impl Renderer for NullRenderer {
+    fn set_colour(&mut self, _colour: RGB) {}
+
+    fn clear(&mut self) {}
+
+    fn draw_rect(&mut self, _x: i32, _y: i32, _w: u32, _h: u32, _colour: RGB) {}
+
+    fn draw_lines(&mut self, _vertices: &[Point], _colour: RGB) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn draw_text(&mut self, _text: &str, _colour: RGB, _position: (i32, i32)) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn draw_vertices(&mut self, _vertices: &[Point], _colour: RGB) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn draw_game_over_screen(&mut self, _score: u32, _seed: u64) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn present(&mut self) {}
+}
+
+#[cfg(feature = "sdl")]
 pub struct SdlRenderer<'a> {
     canvas: &'a mut Canvas<Window>,
     font: &'a sdl2::ttf::Font<'a, 'a>,
@@ -44,6 +89,7 @@ pub struct SdlRenderer<'a> {
     screen_height: u32,
 }
 
+#[cfg(feature = "sdl")]
 impl<'a> SdlRenderer<'a> {
     pub fn new(
         canvas: &'a mut Canvas<Window>,
@@ -60,6 +106,7 @@ impl<'a> SdlRenderer<'a> {
     }
 }
 
+#[cfg(feature = "sdl")]
 impl<'a> Renderer for SdlRenderer<'a> {
     fn set_colour(&mut self, colour: RGB) {
         self.canvas.set_draw_color(colour);
@@ -107,7 +154,7 @@ impl<'a> Renderer for SdlRenderer<'a> {
         Ok(())
     }
 
-    fn draw_game_over_screen(&mut self, score: u32) -> Result<(), String> {
+    fn draw_game_over_screen(&mut self, score: u32, seed: u64) -> Result<(), String> {
         let colour = RGB::WHITE;
 
         let mut text = "GAME OVER";
@@ -131,11 +178,18 @@ impl<'a> Renderer for SdlRenderer<'a> {
         );
         self.draw_text(text, colour, position)?;
 
+        let seed_text = format!("SEED: {}", seed);
+        position = (
+            (self.screen_width / 2 - 250) as i32,
+            (self.screen_height / 2 + 100) as i32,
+        );
+        self.draw_text(&seed_text, colour, position)?;
+
         Ok(())
     }
 
-    fn draw_vertices(&mut self, vertices: &Vec<Point>, colour: RGB) -> Result<(), String> {
-        let mut sorted_vertices = vertices.clone();
+    fn draw_vertices(&mut self, vertices: &[Point], colour: RGB) -> Result<(), String> {
+        let mut sorted_vertices = vertices.to_vec();
         sorted_vertices.sort_by_key(|point| point.y);
 
         let (x1, y1) = (sorted_vertices[0].x, sorted_vertices[0].y);
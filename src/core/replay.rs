@@ -0,0 +1,214 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use super::input::{Command, InputController};
+
+const MAGIC: &[u8; 4] = b"ASTR";
+const VERSION: u8 = 2;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 8 + 1 + 4 + 4;
+
+#[derive(Debug, PartialEq)]
+pub struct Recording {
+    pub seed: u64,
+    /// Score at the last recorded tick, checked when the replay finishes.
+    pub final_score: Option<u32>,
+    ticks: Vec<u8>,
+}
+
+impl Recording {
+    pub fn new(seed: u64) -> Self {
+        Recording { seed, final_score: None, ticks: Vec::new() }
+    }
+
+    pub fn record(&mut self, commands: &[Command]) {
+        self.ticks.push(encode(commands));
+    }
+
+    pub fn len(&self) -> usize {
+        self.ticks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ticks.is_empty()
+    }
+
+    pub fn commands(&self, tick: usize) -> Vec<Command> {
+        self.ticks.get(tick).map_or_else(Vec::new, |&mask| decode(mask))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.ticks.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.push(self.final_score.is_some() as u8);
+        bytes.extend_from_slice(&self.final_score.unwrap_or(0).to_le_bytes());
+        bytes.extend_from_slice(&(self.ticks.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.ticks);
+        fs::write(path, bytes).map_err(|e| e.to_string())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let bytes = fs::read(path).map_err(|e| e.to_string())?;
+        Recording::from_bytes(&bytes)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() <= MAGIC.len() || &bytes[..MAGIC.len()] != MAGIC {
+            return Err("not a replay file".to_string());
+        }
+        if bytes[4] != VERSION {
+            return Err(format!("unsupported replay version {}", bytes[4]));
+        }
+        if bytes.len() < HEADER_LEN {
+            return Err("replay header is truncated".to_string());
+        }
+
+        let seed = u64::from_le_bytes(bytes[5..13].try_into().unwrap());
+        let final_score = (bytes[13] != 0).then(|| u32::from_le_bytes(bytes[14..18].try_into().unwrap()));
+        let len = u32::from_le_bytes(bytes[18..HEADER_LEN].try_into().unwrap()) as usize;
+        let ticks = bytes[HEADER_LEN..].to_vec();
+        if ticks.len() != len {
+            return Err(format!("replay is truncated: expected {} ticks, found {}", len, ticks.len()));
+        }
+
+        Ok(Recording { seed, final_score, ticks })
+    }
+}
+
+/// Where to record the `game`th game of a session: the first keeps `path`,
+/// later ones get a number before the extension (`run.replay`, `run-2.replay`).
+pub fn numbered_path(path: impl AsRef<Path>, game: usize) -> PathBuf {
+    let path = path.as_ref();
+    if game <= 1 {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().map_or_else(Default::default, |stem| stem.to_string_lossy());
+    let name = match path.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, game, extension.to_string_lossy()),
+        None => format!("{}-{}", stem, game),
+    };
+    path.with_file_name(name)
+}
+
+pub struct ReplayController {
+    recording: Recording,
+    tick: usize,
+}
+
+impl ReplayController {
+    pub fn new(recording: Recording) -> Self {
+        ReplayController { recording, tick: 0 }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.recording.seed
+    }
+
+    pub fn expected_score(&self) -> Option<u32> {
+        self.recording.final_score
+    }
+
+    pub fn advance(&mut self) {
+        self.tick += 1;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.tick >= self.recording.len()
+    }
+}
+
+impl InputController for ReplayController {
+    fn poll(&self) -> Vec<Command> {
+        self.recording.commands(self.tick)
+    }
+}
+
+fn encode(commands: &[Command]) -> u8 {
+    commands.iter().fold(0, |mask, &cmd| mask | bit(cmd))
+}
+
+fn decode(mask: u8) -> Vec<Command> {
+    Command::ALL.into_iter().filter(|&cmd| mask & bit(cmd) != 0).collect()
+}
+
+fn bit(cmd: Command) -> u8 {
+    let index = Command::ALL.iter().position(|&c| c == cmd).unwrap();
+    1 << index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Recording {
+        let mut recording = Recording::new(42);
+        recording.record(&[]);
+        recording.record(&[Command::Fire, Command::RotateLeft]);
+        recording.record(&Command::ALL);
+        recording.final_score = Some(1234);
+        recording
+    }
+
+    fn to_bytes(recording: &Recording) -> Vec<u8> {
+        let path = std::env::temp_dir().join(format!("asteroids-replay-bytes-{}-{:?}", std::process::id(), std::thread::current().id()));
+        recording.save(&path).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn commands_survive_encoding() {
+        for cmd in Command::ALL {
+            assert_eq!(decode(encode(&[cmd])), vec![cmd]);
+        }
+        assert_eq!(decode(encode(&Command::ALL)), Command::ALL.to_vec());
+        assert!(decode(encode(&[])).is_empty());
+    }
+
+    #[test]
+    fn recording_round_trips_through_a_file() {
+        let recording = sample();
+        let path = std::env::temp_dir().join(format!("asteroids-replay-round-trip-{}", std::process::id()));
+        recording.save(&path).unwrap();
+        let loaded = Recording::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, recording);
+        assert_eq!(loaded.commands(1), vec![Command::RotateLeft, Command::Fire]);
+        assert!(loaded.commands(3).is_empty());
+    }
+
+    #[test]
+    fn later_games_get_numbered_paths() {
+        assert_eq!(numbered_path("out/run.replay", 1), PathBuf::from("out/run.replay"));
+        assert_eq!(numbered_path("out/run.replay", 3), PathBuf::from("out/run-3.replay"));
+        assert_eq!(numbered_path("run", 2), PathBuf::from("run-2"));
+    }
+
+    #[test]
+    fn bad_magic_is_rejected() {
+        let mut bytes = to_bytes(&sample());
+        bytes[0] = b'X';
+        assert_eq!(Recording::from_bytes(&bytes).unwrap_err(), "not a replay file");
+        assert_eq!(Recording::from_bytes(b"AST").unwrap_err(), "not a replay file");
+    }
+
+    #[test]
+    fn unknown_version_is_rejected() {
+        let mut bytes = to_bytes(&sample());
+        bytes[4] = 9;
+        assert_eq!(Recording::from_bytes(&bytes).unwrap_err(), "unsupported replay version 9");
+    }
+
+    #[test]
+    fn truncated_files_are_rejected() {
+        let bytes = to_bytes(&sample());
+
+        assert_eq!(Recording::from_bytes(&bytes[..10]).unwrap_err(), "replay header is truncated");
+        assert_eq!(
+            Recording::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            "replay is truncated: expected 3 ticks, found 2",
+        );
+    }
+}
@@ -1,16 +1,38 @@
-use std::time::{Instant, Duration};
+use std::time::Duration;
 
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::broadphase::SpatialHash;
 use crate::core::colour::RGB;
-use crate::core::input::{Command, InputController};
+use crate::core::input::{Command, DemoController, InputController, MenuCommand};
 use crate::core::renderer::Renderer;
+use crate::highscores::{self, HighScore, HighScores, InitialsEntry};
 use crate::player::Player;
-use crate::laser::Laser;
-use crate::asteroid::{Asteroid, AsteroidConstructor};
+use crate::laser::{self, Faction, Laser};
+use crate::asteroid::{Asteroid, AsteroidConstructor, AsteroidSize};
+use crate::geometry::Vec2;
+use crate::particles::Particles;
+use crate::saucer::{Saucer, SaucerSize};
+use crate::menu::{Menu, MenuItem};
+use crate::settings::{Settings, ShipStyle};
+use crate::sound::Sound;
+use crate::utils;
+use crate::scoring::{ScoreTable, Scoring, Target};
+use crate::wave::DifficultyCurve;
+
+pub const TIMESTEP: Duration = Duration::from_nanos(1_000_000_000 / 120);
+const HYPERSPACE_ATTEMPTS: usize = 8;
+const HYPERSPACE_CLEARANCE: f64 = 80.0;
+const TITLE_PAGE_DURATION: Duration = Duration::from_secs(10);
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum State {
+    Title,
+    Options,
+    HighScores,
     Playing,
     Paused,
+    EnterInitials,
     GameOver,
 }
 
@@ -19,121 +41,525 @@ pub struct GameState {
     pub state: State,
     pub player: Player,
     pub asteroids: Vec<Asteroid>,
+    pub saucers: Vec<Saucer>,
+    pub particles: Particles,
+    pub settings: Settings,
+    pub menu: Menu,
+    pub high_scores: HighScores,
+    pub initials: InitialsEntry,
+    pub next_seed: Option<u64>,
+    pub scoring: Scoring,
+    pub difficulty: DifficultyCurve,
+    pub wave: u32,
+    intermission: Option<Duration>,
+    pub hyperspace_failure_chance: f64,
+    pub laser_max_distance: f64,
+    pub laser_ttl: Duration,
+    pub seed: u64,
+    rng: StdRng,
     screen_width: u32,
     screen_height: u32,
-    lasers: Vec<Laser>,
-    n_parent_asteroids: usize,
-    max_parent_asteroids: usize,
-    max_lasers: usize,
-    last_fired_time: Instant,
+    pub lasers: Vec<Laser>,
+    time_until_saucer: Duration,
+    broadphase: SpatialHash,
+    max_player_lasers: usize,
+    max_enemy_lasers: usize,
+    time_since_fired: Duration,
     firing_interval: Duration,
+    fire_was_held: bool,
+    time_since_hyperspace: Duration,
+    hyperspace_cooldown: Duration,
+    demo_tick: u64,
+    title_page_time: Duration,
+    sounds: Vec<Sound>,
 }
 
 impl GameState {
-    pub fn new(screen_width: u32, screen_height: u32) -> Self {
+    pub fn new(screen_width: u32, screen_height: u32, seed: u64) -> Self {
         GameState {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
             screen_width,
             screen_height,
             running: true,
             state: State::Playing,
             asteroids: Vec::new(),
+            saucers: Vec::new(),
+            particles: Particles::new(seed),
+            settings: Settings::default(),
+            menu: Menu::title(),
+            high_scores: HighScores::new(),
+            initials: InitialsEntry::new(),
+            next_seed: None,
             player: Player::new(screen_width as f64 / 2.0, screen_height as f64 / 2.0),
             lasers: Vec::new(),
-            n_parent_asteroids: 0,
-            max_parent_asteroids: 7,
-            max_lasers: 64,
-            last_fired_time: Instant::now(),
+            time_until_saucer: DifficultyCurve::default().first_saucer_delay(),
+            broadphase: SpatialHash::new(screen_width, screen_height, 100.0),
+            scoring: Scoring::new(ScoreTable::default()),
+            difficulty: DifficultyCurve::default(),
+            wave: 0,
+            intermission: None,
+            max_player_lasers: 64,
+            max_enemy_lasers: 16,
+            time_since_fired: Duration::MAX,
             firing_interval: Duration::from_millis(350),
+            fire_was_held: false,
+            hyperspace_failure_chance: 1.0 / 16.0,
+            laser_max_distance: laser::DEFAULT_MAX_DISTANCE,
+            laser_ttl: laser::DEFAULT_TTL,
+            time_since_hyperspace: Duration::MAX,
+            hyperspace_cooldown: Duration::from_secs(2),
+            demo_tick: 0,
+            title_page_time: Duration::ZERO,
+            sounds: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, controller: &impl InputController, dt: f64) {
+        match self.state {
+            State::Title | State::Options | State::HighScores => self.update_attract(dt),
+            _ => self.step(controller, dt),
+        }
+    }
+
+    /// Sounds raised since the last call, for the front end to play.
+    pub fn take_sounds(&mut self) -> Vec<Sound> {
+        std::mem::take(&mut self.sounds)
+    }
+
+    pub fn is_simulating(&self) -> bool {
+        !matches!(self.state, State::Paused | State::EnterInitials | State::GameOver)
+    }
+
+    fn update_attract(&mut self, dt: f64) {
+        let controller = DemoController::new(self.demo_tick);
+        self.demo_tick += 1;
+        self.step(&controller, dt);
+        self.sounds.clear();
+        if self.player.is_dead() {
+            let seed = self.rng.gen();
+            self.reset(seed);
+        }
+
+        self.title_page_time = self.title_page_time.saturating_add(Duration::from_secs_f64(dt));
+        if self.title_page_time >= TITLE_PAGE_DURATION {
+            match self.state {
+                State::Title => self.show_menu(State::HighScores, Menu::title()),
+                State::HighScores => self.show_menu(State::Title, Menu::title()),
+                _ => self.title_page_time = Duration::ZERO,
+            }
         }
     }
 
-    pub fn update(&mut self, controller: &impl InputController) {
-        self.add_asteroids();
+    fn step(&mut self, controller: &impl InputController, dt: f64) {
+        self.advance_wave(dt);
         for asteroid in self.asteroids.iter_mut() {
-            asteroid.update(self.screen_width, self.screen_height)
+            asteroid.update(dt, self.screen_width, self.screen_height)
         }
-        self.player.update(controller, self.screen_width, self.screen_height);
-        self.handle_firing(controller);
+        self.update_saucers(dt);
+        self.particles.update(dt);
+        self.player.update(controller, dt, self.screen_width, self.screen_height);
+        self.handle_hyperspace(controller, dt);
+        self.handle_firing(controller, dt);
+        self.handle_respawn(controller);
+        self.scoring.update(dt);
+        self.rebuild_broadphase();
         self.handle_asteroid_hits();
+        self.handle_saucer_hits();
+        self.handle_saucer_collisions();
+        self.handle_laser_player_hits();
         self.handle_player_collision();
     }
 
-    pub fn draw(&self, renderer: &mut impl Renderer) -> Result<(), String> {
+    pub fn draw(&self, renderer: &mut impl Renderer, alpha: f64) -> Result<(), String> {
         let white = RGB::WHITE;
 
-        self.player.draw(renderer, white)?;
-        self.player.draw_score(renderer, white)?;
-        self.player.draw_lives(renderer, self.screen_width, white)?;
+        match self.state {
+            State::GameOver => {
+                renderer.draw_game_over_screen(self.player.score, self.seed)?;
+            }
+            State::EnterInitials => {
+                self.draw_initials_entry(renderer, white)?;
+            }
+            State::Title | State::Options | State::HighScores => {
+                self.draw_world(renderer, white, alpha)?;
+                self.draw_front_end(renderer, white)?;
+            }
+            State::Playing | State::Paused => {
+                self.draw_world(renderer, white, alpha)?;
+                self.draw_hud(renderer, white)?;
+                if self.state == State::Paused {
+                    self.draw_paused_screen(renderer, white)?;
+                }
+            }
+        }
+
+        renderer.present();
+        Ok(())
+    }
+
+    pub fn handle_menu(&mut self, command: MenuCommand) {
+        match (self.state, command) {
+            (State::Playing, MenuCommand::Back) => {
+                self.menu = Menu::pause();
+                self.toggle_paused();
+            }
+            (State::Paused, MenuCommand::Back) => self.toggle_paused(),
+            (State::GameOver, MenuCommand::Select) => self.start_game(),
+            (State::GameOver, MenuCommand::Back) => self.enter_title(),
+            (State::EnterInitials, MenuCommand::Up) => self.initials.next_letter(),
+            (State::EnterInitials, MenuCommand::Down) => self.initials.previous_letter(),
+            (State::EnterInitials, MenuCommand::Left | MenuCommand::Back) => self.initials.left(),
+            (State::EnterInitials, MenuCommand::Right) => self.initials.right(),
+            (State::EnterInitials, MenuCommand::Select) if self.initials.is_on_last_letter() => self.record_high_score(),
+            (State::EnterInitials, MenuCommand::Select) => self.initials.right(),
+            (State::HighScores, MenuCommand::Select | MenuCommand::Back) => self.show_menu(State::Title, Menu::title()),
+            (State::Options, MenuCommand::Back) => self.show_menu(State::Title, Menu::title()),
+            (State::Title | State::Options | State::Paused, MenuCommand::Up) => self.menu.previous(),
+            (State::Title | State::Options | State::Paused, MenuCommand::Down) => self.menu.next(),
+            (State::Title | State::Options | State::Paused, MenuCommand::Select) => self.activate(self.menu.selected()),
+            (State::Options, MenuCommand::Left | MenuCommand::Right) if self.menu.selected() == MenuItem::ShipStyle => {
+                self.activate(MenuItem::ShipStyle)
+            }
+            _ => {}
+        }
+    }
+
+    pub fn start_game(&mut self) {
+        let seed = self.next_seed.take().unwrap_or_else(rand::random);
+        self.reset(seed);
+        self.state = State::Playing;
+    }
+
+    pub fn enter_title(&mut self) {
+        let seed = self.rng.gen();
+        self.reset(seed);
+        self.show_menu(State::Title, Menu::title());
+    }
+
+    fn show_menu(&mut self, state: State, menu: Menu) {
+        self.state = state;
+        self.menu = menu;
+        self.title_page_time = Duration::ZERO;
+    }
+
+    fn record_high_score(&mut self) {
+        self.high_scores.insert(HighScore {
+            initials: self.initials.initials(),
+            score: self.player.score,
+            date: highscores::today(),
+            wave: self.wave,
+            seed: self.seed,
+        });
+        self.enter_title();
+        self.show_menu(State::HighScores, Menu::title());
+    }
+
+    fn end_game(&mut self) {
+        if self.high_scores.qualifies(self.player.score) {
+            self.initials = InitialsEntry::new();
+            self.state = State::EnterInitials;
+        } else {
+            self.state = State::GameOver;
+        }
+    }
+
+    fn activate(&mut self, item: MenuItem) {
+        match item {
+            MenuItem::Play => self.start_game(),
+            MenuItem::Options => self.show_menu(State::Options, Menu::options()),
+            MenuItem::HighScores => self.show_menu(State::HighScores, Menu::title()),
+            MenuItem::Quit => self.running = false,
+            MenuItem::ShipStyle => {
+                self.settings.ship_style = match self.settings.ship_style {
+                    ShipStyle::Filled => ShipStyle::Outline,
+                    ShipStyle::Outline => ShipStyle::Filled,
+                };
+            }
+            MenuItem::Back => self.show_menu(State::Title, Menu::title()),
+            MenuItem::Resume => self.toggle_paused(),
+            MenuItem::QuitToTitle => self.enter_title(),
+        }
+    }
+
+    fn draw_hud(&self, renderer: &mut impl Renderer, color: RGB) -> Result<(), String> {
+        self.player.draw_score(renderer, color)?;
+        self.player.draw_lives(renderer, self.screen_width, color)?;
+        self.player.draw_shield_meter(renderer, color)?;
+        self.draw_wave(renderer, color)
+    }
+
+    fn draw_world(&self, renderer: &mut impl Renderer, white: RGB, alpha: f64) -> Result<(), String> {
+        self.particles.draw(renderer, white, alpha)?;
+        self.player.draw(renderer, white, alpha, self.settings.ship_style)?;
 
         for asteroid in &self.asteroids {
-            asteroid.draw(renderer, white)?;
+            asteroid.draw(renderer, white, alpha)?;
+        }
+
+        for saucer in &self.saucers {
+            saucer.draw(renderer, white, alpha)?;
         }
 
         for laser in &self.lasers {
-            laser.draw(renderer, white)?;
+            laser.draw(renderer, alpha)?;
         }
+        Ok(())
+    }
 
-        if self.state == State::Paused {
-            self.draw_paused_screen(renderer, white)?;
+    fn draw_front_end(&self, renderer: &mut impl Renderer, color: RGB) -> Result<(), String> {
+        let top = (0.5*self.screen_height as f32) as i32 - 160;
+        match self.state {
+            State::Title => {
+                self.draw_centred_text(renderer, "ASTEROIDS", color, top)?;
+                self.draw_menu(renderer, color, top + 120)
+            }
+            State::Options => {
+                self.draw_centred_text(renderer, "OPTIONS", color, top)?;
+                self.draw_menu(renderer, color, top + 120)
+            }
+            State::HighScores => self.draw_high_scores(renderer, color),
+            _ => Ok(()),
         }
+    }
 
-        renderer.present();
+    fn draw_high_scores(&self, renderer: &mut impl Renderer, color: RGB) -> Result<(), String> {
+        self.draw_centred_text(renderer, "HIGH SCORES", color, 40)?;
+        if self.high_scores.is_empty() {
+            return self.draw_centred_text(renderer, "NO SCORES YET", color, 160);
+        }
+        for (rank, entry) in self.high_scores.entries().iter().enumerate() {
+            let line = format!("{:>2} {} {:>6} W{:<2} {}", rank + 1, entry.initials, entry.score, entry.wave, entry.date);
+            self.draw_centred_text(renderer, &line, color, 110 + 45 * rank as i32)?;
+        }
         Ok(())
     }
 
-    pub fn handle_firing(&mut self, controller: &impl InputController) {
-        self.lasers.retain(|laser| laser.x >= 0.0 && laser.x <= 800.0 && laser.y >= 0.0 && laser.y <= 600.0);
+    fn draw_initials_entry(&self, renderer: &mut impl Renderer, color: RGB) -> Result<(), String> {
+        let top = (0.5*self.screen_height as f32) as i32 - 160;
+        self.draw_centred_text(renderer, "NEW HIGH SCORE", color, top)?;
+        self.draw_centred_text(renderer, &format!("SCORE: {}", self.player.score), color, top + 60)?;
 
-        for cmd in controller.poll() {
-            match cmd {
-                Command::Fire => self.fire_laser(),
-                _ => {},
+        let initials = self.initials.initials();
+        let left = (self.screen_width as i32 - 48 * initials.len() as i32) / 2;
+        for (index, letter) in initials.chars().enumerate() {
+            let x = left + 48 * index as i32;
+            renderer.draw_text(&letter.to_string(), color, (x, top + 150))?;
+            if index == self.initials.cursor() {
+                renderer.draw_rect(x, top + 190, 28, 4, color);
             }
         }
+        self.draw_centred_text(renderer, "ENTER YOUR INITIALS", color, top + 260)
+    }
+
+    fn draw_menu(&self, renderer: &mut impl Renderer, color: RGB, top: i32) -> Result<(), String> {
+        for (index, &item) in self.menu.items().iter().enumerate() {
+            let label = self.menu_label(item);
+            let y = top + 45 * index as i32;
+            self.draw_centred_text(renderer, &label, color, y)?;
+            if index == self.menu.selected_index() {
+                let x = self.centred_x(&label) - 48;
+                renderer.draw_text(">", color, (x, y))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn menu_label(&self, item: MenuItem) -> String {
+        let label = match item {
+            MenuItem::Play => "PLAY",
+            MenuItem::Options => "OPTIONS",
+            MenuItem::HighScores => "HIGH SCORES",
+            MenuItem::Quit => "QUIT",
+            MenuItem::ShipStyle => match self.settings.ship_style {
+                ShipStyle::Filled => "SHIP: FILLED",
+                ShipStyle::Outline => "SHIP: OUTLINE",
+            },
+            MenuItem::Back => "BACK",
+            MenuItem::Resume => "RESUME",
+            MenuItem::QuitToTitle => "QUIT TO TITLE",
+        };
+        label.to_string()
+    }
+
+    fn draw_centred_text(&self, renderer: &mut impl Renderer, text: &str, color: RGB, y: i32) -> Result<(), String> {
+        renderer.draw_text(text, color, (self.centred_x(text), y))
+    }
+
+    fn centred_x(&self, text: &str) -> i32 {
+        (self.screen_width as i32 - 24 * text.len() as i32) / 2
+    }
+
+    pub fn handle_firing(&mut self, controller: &impl InputController, dt: f64) {
+        self.lasers.retain(|laser| !laser.is_expired());
+        self.time_since_fired = self.time_since_fired.saturating_add(Duration::from_secs_f64(dt));
+
+        if controller.poll().contains(&Command::Fire) && !self.player.is_respawning() {
+            self.fire_laser();
+        }
 
         for laser in self.lasers.iter_mut() {
-            laser.update();
+            laser.update(dt, self.screen_width, self.screen_height);
+        }
+    }
+
+    pub fn handle_hyperspace(&mut self, controller: &impl InputController, dt: f64) {
+        self.time_since_hyperspace = self.time_since_hyperspace.saturating_add(Duration::from_secs_f64(dt));
+        if self.player.is_respawning() {
+            return;
+        }
+        if !controller.poll().contains(&Command::Hyperspace) || self.time_since_hyperspace < self.hyperspace_cooldown {
+            return;
+        }
+        self.time_since_hyperspace = Duration::ZERO;
+
+        let (x, y) = self.find_hyperspace_destination();
+        self.player.hyperspace(x, y);
+        if self.rng.gen_bool(self.hyperspace_failure_chance) {
+            self.damage_player();
+        }
+    }
+
+    pub fn handle_respawn(&mut self, controller: &impl InputController) {
+        let fire_held = controller.poll().contains(&Command::Fire);
+        let fire_pressed = fire_held && !self.fire_was_held;
+        self.fire_was_held = fire_held;
+
+        if !self.player.is_respawning() {
+            return;
+        }
+        if fire_pressed || self.is_area_clear(self.player.position(), self.player.spawn_zone_radius()) {
+            self.player.respawn();
+        }
+    }
+
+    pub fn is_area_clear(&self, centre: Vec2, radius: f64) -> bool {
+        let outside = |(min, max): (Vec2, Vec2)| {
+            centre.x + radius < min.x || centre.x - radius > max.x
+                || centre.y + radius < min.y || centre.y - radius > max.y
+        };
+        self.asteroids.iter().all(|asteroid| outside(asteroid.bounds()))
+            && self.saucers.iter().all(|saucer| outside(saucer.bounds()))
+    }
+
+    pub fn update_saucers(&mut self, dt: f64) {
+        if self.saucers.is_empty() && self.wave > 0 && !self.is_intermission() {
+            let elapsed = Duration::from_secs_f64(dt);
+            if self.time_until_saucer <= elapsed {
+                self.spawn_saucer();
+            } else {
+                self.time_until_saucer -= elapsed;
+            }
+        }
+
+        let target = self.player.position();
+        let mut enemy_lasers = self.laser_count(Faction::Enemy);
+        for saucer in self.saucers.iter_mut() {
+            saucer.update(&mut self.rng, dt, self.screen_height);
+            if enemy_lasers >= self.max_enemy_lasers {
+                continue;
+            }
+            if let Some(laser) = saucer.fire(&mut self.rng, target) {
+                self.lasers.push(laser.max_distance(self.laser_max_distance).ttl(self.laser_ttl));
+                enemy_lasers += 1;
+            }
+        }
+        let screen_width = self.screen_width;
+        self.saucers.retain(|saucer| !saucer.has_left_screen(screen_width));
+    }
+
+    pub fn laser_count(&self, owner: Faction) -> usize {
+        self.lasers.iter().filter(|laser| laser.owner == owner).count()
+    }
+
+    pub fn advance_wave(&mut self, dt: f64) {
+        match self.intermission {
+            Some(remaining) => {
+                let remaining = remaining.saturating_sub(Duration::from_secs_f64(dt));
+                if remaining.is_zero() {
+                    self.intermission = None;
+                    self.add_asteroids();
+                } else {
+                    self.intermission = Some(remaining);
+                }
+            }
+            None if self.asteroids.is_empty() => {
+                self.wave += 1;
+                self.intermission = Some(self.difficulty.intermission);
+            }
+            None => {}
         }
     }
 
     pub fn add_asteroids(&mut self) {
-        while self.n_parent_asteroids < self.max_parent_asteroids {
-            let asteroid = AsteroidConstructor::new(self.screen_width, self.screen_height).parent(true).build();
+        let speed_multiplier = self.difficulty.speed_multiplier(self.wave);
+        for _ in 0..self.difficulty.asteroid_count(self.wave) {
+            let asteroid = AsteroidConstructor::new(self.screen_width, self.screen_height)
+                .size(AsteroidSize::Large)
+                .speed_multiplier(speed_multiplier)
+                .build(&mut self.rng);
             self.asteroids.push(asteroid);
-            self.n_parent_asteroids += 1;
         }
     }
 
+    pub fn is_intermission(&self) -> bool {
+        self.intermission.is_some()
+    }
+
     pub fn handle_asteroid_hits(&mut self) {
         let mut asteroids_to_destroy = Vec::new();
-        let mut asteroids_to_spawn = Vec::new();
         let mut lasers_to_remove = Vec::new();
 
         for (laser_index, laser) in self.lasers.iter().enumerate() {
-            for (index, asteroid) in self.asteroids.iter().enumerate() {
-                if asteroid.is_hit(laser.x, laser.y) {
-                    asteroids_to_destroy.push(index);
-                    lasers_to_remove.push(laser_index);
-                    self.player.increment_score();
-                    if asteroid.parent {
-                        self.n_parent_asteroids -= 1;
-                        asteroids_to_spawn.push(index);
+            let hit = laser.segments().find_map(|(start, end)| {
+                let min = Vec2::new(start.x.min(end.x), start.y.min(end.y));
+                let max = Vec2::new(start.x.max(end.x), start.y.max(end.y));
+                self.broadphase
+                    .query(min, max)
+                    .into_iter()
+                    .find(|index| !asteroids_to_destroy.contains(index) && self.asteroids[*index].is_hit(start, end))
+                    .map(|index| (index, end))
+            });
+            if let Some((index, end)) = hit {
+                self.particles.spawn_sparks(end, 6);
+                asteroids_to_destroy.push(index);
+                lasers_to_remove.push(laser_index);
+                if laser.owner == Faction::Player {
+                    let points = self.scoring.award(Target::Asteroid(self.asteroids[index].size));
+                    if self.player.add_score(points) {
+                        self.sounds.push(Sound::BonusLife);
                     }
                 }
             }
         }
 
-        for index in asteroids_to_spawn.into_iter() {
-            if let Some(asteroid) = self.asteroids.get(index) {
-                let child_1 = asteroid.generate_child(self.screen_width, self.screen_height);
-                let child_2 = asteroid.generate_child(self.screen_width, self.screen_height);
-                self.asteroids.push(child_1);
-                self.asteroids.push(child_2);
-            }
+        self.split_asteroids(asteroids_to_destroy);
+
+        for index in lasers_to_remove.into_iter().rev() {
+            self.remove_laser(index);
         }
+    }
 
-        for index in asteroids_to_destroy.into_iter().rev() {
-            self.destroy_asteroid(index);
+    pub fn handle_saucer_hits(&mut self) {
+        let mut lasers_to_remove = Vec::new();
+
+        for (laser_index, laser) in self.lasers.iter().enumerate() {
+            if !laser.owner.is_hostile_to(Faction::Enemy) {
+                continue;
+            }
+            let hit = laser.segments().find_map(|(start, end)| {
+                self.saucers.iter().position(|saucer| saucer.is_hit(start, end)).map(|index| (index, end))
+            });
+            if let Some((index, end)) = hit {
+                let saucer = self.saucers.remove(index);
+                self.particles.spawn_sparks(end, 6);
+                self.particles.spawn_debris(saucer.vertices());
+                let points = self.scoring.award(Target::Saucer(saucer.size));
+                if self.player.add_score(points) {
+                    self.sounds.push(Sound::BonusLife);
+                }
+                lasers_to_remove.push(laser_index);
+            }
         }
 
         for index in lasers_to_remove.into_iter().rev() {
@@ -141,17 +567,78 @@ impl GameState {
         }
     }
 
+    pub fn handle_saucer_collisions(&mut self) {
+        let mut asteroids_to_destroy = Vec::new();
+        let broadphase = &self.broadphase;
+        let asteroids = &self.asteroids;
+        let particles = &mut self.particles;
+
+        self.saucers.retain(|saucer| {
+            let (min, max) = saucer.bounds();
+            let collision = broadphase.query(min, max).into_iter().find(|&index| {
+                !asteroids_to_destroy.contains(&index) && asteroids[index].is_colliding(saucer.vertices())
+            });
+            if let Some(index) = collision {
+                asteroids_to_destroy.push(index);
+                particles.spawn_debris(saucer.vertices());
+            }
+            collision.is_none()
+        });
+
+        self.split_asteroids(asteroids_to_destroy);
+
+        if self.player.is_invulnerable() {
+            return;
+        }
+        if let Some(index) = self.saucers.iter().position(|saucer| saucer.is_colliding(&self.player.vertices)) {
+            let saucer = self.saucers.remove(index);
+            self.particles.spawn_debris(saucer.vertices());
+            let points = self.scoring.award(Target::Saucer(saucer.size));
+            if self.player.add_score(points) {
+                self.sounds.push(Sound::BonusLife);
+            }
+            self.damage_player();
+        }
+    }
+
+    // Like asteroids, enemy lasers pass through a ship that can't be hurt.
+    pub fn handle_laser_player_hits(&mut self) {
+        if self.player.is_invulnerable() {
+            return;
+        }
+        let player = &self.player.vertices;
+        let lasers_before = self.lasers.len();
+        self.lasers.retain(|laser| {
+            !(laser.owner.is_hostile_to(Faction::Player)
+                && laser.segments().any(|(start, end)| utils::segment_polygon_collision(start, end, player)))
+        });
+        if self.lasers.len() < lasers_before {
+            self.damage_player();
+        }
+    }
+
     pub fn handle_player_collision(&mut self) {
-        for asteroid in self.asteroids.iter() {
-            if asteroid.is_colliding(&self.player.vertices) {
-                self.player.hit(self.screen_width, self.screen_height);
-                if self.player.is_dead() {
-                    self.state = State::GameOver
-                }
+        if self.player.is_shielded() {
+            self.bounce_asteroids_off_shield();
+            return;
+        }
+        let (min, max) = self.player.vertices.bounds();
+        for index in self.broadphase.query(min, max) {
+            if self.asteroids[index].is_colliding(&self.player.vertices) {
+                self.damage_player();
             }
         }
     }
 
+    fn bounce_asteroids_off_shield(&mut self) {
+        let centre = self.player.position();
+        let radius = self.player.shield_radius();
+        let extent = Vec2::new(radius, radius);
+        for index in self.broadphase.query(centre - extent, centre + extent) {
+            self.asteroids[index].bounce_off(centre, radius);
+        }
+    }
+
     pub fn toggle_paused(&mut self) {
         if self.state == State::Playing {
             self.state = State::Paused
@@ -160,21 +647,99 @@ impl GameState {
         }
     }
 
+    pub fn draw_wave(&self, renderer: &mut impl Renderer, color: RGB) -> Result<(), String> {
+        if self.wave == 0 {
+            return Ok(());
+        }
+        let text = format!("WAVE {}", self.wave);
+        let hud_position = ((0.5*self.screen_width as f32) as i32 - 60, 25);
+        renderer.draw_text(&text, color, hud_position)?;
+
+        if self.is_intermission() {
+            let banner_position = ((0.5*self.screen_width as f32) as i32 - 60, (0.5*self.screen_height as f32) as i32 - 80);
+            renderer.draw_text(&text, color, banner_position)?;
+        }
+        Ok(())
+    }
+
     pub fn draw_paused_screen(&self,  renderer: &mut impl Renderer, color: RGB) -> Result<(), String> {
         let text = "PAUSED";
         let x_offset = -50;
         let y_offset = -20;
         let position: (i32, i32) = ((0.5*self.screen_width as f32) as i32 + x_offset, (0.5*self.screen_height as f32) as i32 + y_offset);
-        renderer.draw_text(&text, color, position)?;
-        Ok(())
+        renderer.draw_text(text, color, position)?;
+        self.draw_menu(renderer, color, position.1 + 70)
     }
 
-    pub fn reset(&mut self) {
+    pub fn reset(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+        self.time_since_fired = Duration::MAX;
+        self.time_since_hyperspace = Duration::MAX;
+        self.fire_was_held = false;
+        self.scoring.reset();
+        self.wave = 0;
+        self.intermission = None;
         self.asteroids.clear();
+        self.saucers.clear();
+        self.particles.reset(seed);
         self.lasers.clear();
+        self.sounds.clear();
+        self.time_until_saucer = self.difficulty.first_saucer_delay();
         self.player.reset(self.screen_width, self.screen_height);
     }
 
+    fn rebuild_broadphase(&mut self) {
+        self.broadphase.clear();
+        for (index, asteroid) in self.asteroids.iter().enumerate() {
+            let (min, max) = asteroid.bounds();
+            self.broadphase.insert(index, min, max);
+        }
+    }
+
+    fn split_asteroids(&mut self, mut indices: Vec<usize>) {
+        if indices.is_empty() {
+            return;
+        }
+
+        for &index in &indices {
+            self.particles.spawn_dots(self.asteroids[index].position(), 10);
+            for _ in 0..2 {
+                if let Some(child) = self.asteroids[index].generate_child(&mut self.rng, self.screen_width, self.screen_height) {
+                    self.asteroids.push(child);
+                }
+            }
+        }
+
+        indices.sort_unstable();
+        for index in indices.into_iter().rev() {
+            self.destroy_asteroid(index);
+        }
+        self.rebuild_broadphase();
+    }
+
+    fn spawn_saucer(&mut self) {
+        let size = if self.rng.gen_bool(self.difficulty.small_saucer_chance(self.wave)) {
+            SaucerSize::Small
+        } else {
+            SaucerSize::Large
+        };
+        self.saucers.push(Saucer::spawn(&mut self.rng, size, self.screen_width, self.screen_height));
+        self.time_until_saucer = self.difficulty.saucer_delay(&mut self.rng);
+    }
+
+    fn damage_player(&mut self) {
+        let outline = self.player.vertices.clone();
+        let lives = self.player.lives();
+        self.player.hit(self.screen_width, self.screen_height);
+        if self.player.lives() < lives {
+            self.particles.spawn_debris(&outline);
+        }
+        if self.player.is_dead() && self.state == State::Playing {
+            self.end_game();
+        }
+    }
+
     fn destroy_asteroid(&mut self, index: usize) {
         self.asteroids.remove(index);
     }
@@ -183,13 +748,64 @@ impl GameState {
         self.lasers.remove(index);
     }
 
-    fn fire_laser(&mut self) {
-        if self.last_fired_time.elapsed() >= self.firing_interval {
-            if self.lasers.len() < self.max_lasers {
-                self.lasers.push(self.player.fire());
-                self.last_fired_time = Instant::now();
+    fn find_hyperspace_destination(&mut self) -> (f64, f64) {
+        // On screens too small for the margin the range collapses to the centre.
+        let width = self.screen_width as f64;
+        let height = self.screen_height as f64;
+        let margin_x = (HYPERSPACE_CLEARANCE / 2.0).min(width / 2.0);
+        let margin_y = (HYPERSPACE_CLEARANCE / 2.0).min(height / 2.0);
+        let mut destination = (width / 2.0, height / 2.0);
+        for _ in 0..HYPERSPACE_ATTEMPTS {
+            let x = self.rng.gen_range(margin_x..=width - margin_x);
+            let y = self.rng.gen_range(margin_y..=height - margin_y);
+            destination = (x, y);
+            if self.is_area_clear(Vec2::new(x, y), HYPERSPACE_CLEARANCE) {
+                break;
             }
         }
+        destination
     }
 
+    fn fire_laser(&mut self) {
+        if self.time_since_fired >= self.firing_interval && self.laser_count(Faction::Player) < self.max_player_lasers {
+            let laser = self.player.fire().max_distance(self.laser_max_distance).ttl(self.laser_ttl);
+            self.lasers.push(laser);
+            self.time_since_fired = Duration::ZERO;
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn still_asteroid(x: f64, y: f64, size: AsteroidSize) -> Asteroid {
+        let mut asteroid = AsteroidConstructor::new(800, 600)
+            .x(x)
+            .y(y)
+            .velocity_x(20.0)
+            .velocity_y(20.0)
+            .size(size)
+            .build(&mut StdRng::seed_from_u64(0));
+        asteroid.update(0.0, 800, 600);
+        asteroid
+    }
+
+    #[test]
+    fn two_lasers_in_one_asteroid_destroy_it_once() {
+        let mut game_state = GameState::new(800, 600, 0);
+        game_state.asteroids.push(still_asteroid(200.0, 200.0, AsteroidSize::Large));
+        game_state.asteroids.push(still_asteroid(600.0, 400.0, AsteroidSize::Small));
+        game_state.lasers.push(Laser::new(200.0, 200.0, 0.0, Faction::Player));
+        game_state.lasers.push(Laser::new(200.0, 200.0, 0.0, Faction::Player));
+
+        game_state.rebuild_broadphase();
+        game_state.handle_asteroid_hits();
+
+        assert_eq!(game_state.player.score, game_state.scoring.table.large_asteroid);
+        assert_eq!(game_state.asteroids.len(), 3);
+        assert_eq!(game_state.lasers.len(), 1);
+    }
 }
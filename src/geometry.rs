@@ -0,0 +1,125 @@
+use std::ops::{Add, AddAssign, Mul, Sub};
+
+use crate::core::renderer::Point;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Vec2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Vec2 {
+    pub const ZERO: Self = Self { x: 0.0, y: 0.0 };
+
+    pub fn new(x: f64, y: f64) -> Self {
+        Vec2 { x, y }
+    }
+
+    pub fn from_angle(angle: f64, length: f64) -> Self {
+        Vec2::new(length * angle.cos(), length * angle.sin())
+    }
+
+    pub fn dot(self, other: Vec2) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn cross(self, other: Vec2) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn rotated(self, angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Vec2::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Vec2) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<f64> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, scale: f64) -> Vec2 {
+        Vec2::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl From<Vec2> for Point {
+    fn from(v: Vec2) -> Self {
+        Point::new(v.x as i32, v.y as i32)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polygon {
+    pub vertices: Vec<Vec2>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Vec2>) -> Self {
+        Polygon { vertices }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        let mut min = Vec2::new(f64::INFINITY, f64::INFINITY);
+        let mut max = Vec2::new(f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for v in &self.vertices {
+            min = Vec2::new(min.x.min(v.x), min.y.min(v.y));
+            max = Vec2::new(max.x.max(v.x), max.y.max(v.y));
+        }
+        (min, max)
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        let n = self.vertices.len();
+        (0..n).map(move |i| (self.vertices[i], self.vertices[(i + 1) % n]))
+    }
+
+    pub fn translated(&self, offset: Vec2) -> Polygon {
+        Polygon::new(self.vertices.iter().map(|&v| v + offset).collect())
+    }
+
+    pub fn transformed(&self, angle: f64, offset: Vec2) -> Polygon {
+        Polygon::new(self.vertices.iter().map(|&v| v.rotated(angle) + offset).collect())
+    }
+
+    pub fn to_points(&self) -> Vec<Point> {
+        self.vertices.iter().copied().map(Into::into).collect()
+    }
+
+    pub fn to_closed_points(&self) -> Vec<Point> {
+        let mut points = self.to_points();
+        if let Some(&first) = points.first() {
+            points.push(first);
+        }
+        points
+    }
+}
@@ -0,0 +1,330 @@
+use std::{env, fs, io::ErrorKind, path::{Path, PathBuf}, time::SystemTime};
+
+pub const MAX_ENTRIES: usize = 10;
+const HEADER: &str = "ASTEROIDS HIGH SCORES 1";
+const LETTERS: &[u8; 26] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HighScore {
+    pub initials: String,
+    pub score: u32,
+    pub date: String,
+    pub wave: u32,
+    pub seed: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HighScores {
+    entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    pub fn new() -> Self {
+        HighScores::default()
+    }
+
+    pub fn entries(&self) -> &[HighScore] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0 && (self.entries.len() < MAX_ENTRIES || self.entries.iter().any(|entry| score > entry.score))
+    }
+
+    /// Inserts below any existing entries with the same score and returns the
+    /// entry's rank, or `None` if it did not make the table.
+    pub fn insert(&mut self, entry: HighScore) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+        let rank = self.entries.iter().position(|existing| entry.score > existing.score).unwrap_or(self.entries.len());
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_ENTRIES);
+        Some(rank)
+    }
+
+    /// A missing file is an empty table rather than an error.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        match read(path.as_ref())? {
+            Some(text) => HighScores::parse(&text),
+            None => Ok(HighScores::new()),
+        }
+    }
+
+    /// Like `load`, but a file that can be read and doesn't parse is moved
+    /// aside, so saving can't destroy it; the backup's path is returned with
+    /// an empty table. Errors only when the file can't be read at all.
+    pub fn load_or_back_up(path: impl AsRef<Path>) -> Result<(Self, Option<PathBuf>), String> {
+        let path = path.as_ref();
+        let Some(text) = read(path)? else {
+            return Ok((HighScores::new(), None));
+        };
+        match HighScores::parse(&text) {
+            Ok(high_scores) => Ok((high_scores, None)),
+            Err(_) => Ok((HighScores::new(), Some(back_up(path)?))),
+        }
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines();
+        if lines.next() != Some(HEADER) {
+            return Err("not a high score file".to_string());
+        }
+        let mut high_scores = HighScores::new();
+        for line in lines.filter(|line| !line.trim().is_empty()) {
+            high_scores.insert(parse_entry(line)?);
+        }
+        Ok(high_scores)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let mut text = format!("{}\n", HEADER);
+        for entry in &self.entries {
+            text += &format!("{} {} {} {} {}\n", entry.initials, entry.score, entry.date, entry.wave, entry.seed);
+        }
+        fs::write(path, text).map_err(|e| e.to_string())
+    }
+
+    pub fn default_path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join("asteroids").join("highscores.txt"))
+    }
+}
+
+fn read(path: &Path) -> Result<Option<String>, String> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+// Renames to the first free `<name>.bak`, `<name>.bak.2`, ... so earlier
+// backups survive.
+fn back_up(path: &Path) -> Result<PathBuf, String> {
+    let backup = (1..)
+        .map(|n| {
+            let mut backup = path.as_os_str().to_owned();
+            backup.push(if n == 1 { ".bak".to_string() } else { format!(".bak.{}", n) });
+            PathBuf::from(backup)
+        })
+        .find(|backup| !backup.exists())
+        .unwrap();
+    fs::rename(path, &backup).map_err(|e| e.to_string())?;
+    Ok(backup)
+}
+
+fn parse_entry(line: &str) -> Result<HighScore, String> {
+    let invalid = || format!("invalid high score entry '{}'", line);
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [initials, score, date, wave, seed] = fields[..] else {
+        return Err(invalid());
+    };
+    if initials.len() != 3 || !initials.bytes().all(|b| LETTERS.contains(&b)) {
+        return Err(invalid());
+    }
+    Ok(HighScore {
+        initials: initials.to_string(),
+        score: score.parse().map_err(|_| invalid())?,
+        date: date.to_string(),
+        wave: wave.parse().map_err(|_| invalid())?,
+        seed: seed.parse().map_err(|_| invalid())?,
+    })
+}
+
+fn data_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+    if cfg!(windows) {
+        return env::var_os("APPDATA").map(PathBuf::from);
+    }
+    let share = if cfg!(target_os = "macos") { "Library/Application Support" } else { ".local/share" };
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(share))
+}
+
+/// Today's UTC date as `YYYY-MM-DD`.
+pub fn today() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    date_from_days(seconds / 86_400)
+}
+
+// Days since 1970-01-01 to a civil date, after Howard Hinnant's algorithm.
+fn date_from_days(days: u64) -> String {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Arcade-style entry: Up/Down cycle the letter under the cursor and
+/// Left/Right move between the three slots.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InitialsEntry {
+    letters: [usize; 3],
+    cursor: usize,
+}
+
+impl InitialsEntry {
+    pub fn new() -> Self {
+        InitialsEntry::default()
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn initials(&self) -> String {
+        self.letters.iter().map(|&index| LETTERS[index] as char).collect()
+    }
+
+    pub fn next_letter(&mut self) {
+        let letter = &mut self.letters[self.cursor];
+        *letter = (*letter + 1) % LETTERS.len();
+    }
+
+    pub fn previous_letter(&mut self) {
+        let letter = &mut self.letters[self.cursor];
+        *letter = (*letter + LETTERS.len() - 1) % LETTERS.len();
+    }
+
+    pub fn left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.letters.len() - 1);
+    }
+
+    pub fn is_on_last_letter(&self) -> bool {
+        self.cursor + 1 == self.letters.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(initials: &str, score: u32) -> HighScore {
+        HighScore { initials: initials.to_string(), score, date: "2026-10-18".to_string(), wave: 3, seed: 7 }
+    }
+
+    #[test]
+    fn table_keeps_the_top_ten_in_order() {
+        let mut high_scores = HighScores::new();
+        for score in 1..=12 {
+            high_scores.insert(entry("AAA", score * 100));
+        }
+
+        assert_eq!(high_scores.entries().len(), MAX_ENTRIES);
+        assert_eq!(high_scores.entries()[0].score, 1200);
+        assert_eq!(high_scores.entries()[9].score, 300);
+        assert!(!high_scores.qualifies(300));
+        assert!(high_scores.qualifies(301));
+        assert_eq!(high_scores.insert(entry("BBB", 1200)), Some(1));
+    }
+
+    #[test]
+    fn zero_never_qualifies() {
+        assert!(!HighScores::new().qualifies(0));
+    }
+
+    #[test]
+    fn table_round_trips_through_a_file() {
+        let path = env::temp_dir().join(format!("asteroids-highscores-{}", std::process::id())).join("scores.txt");
+        let mut high_scores = HighScores::new();
+        high_scores.insert(entry("ABC", 1500));
+        high_scores.insert(entry("XYZ", 250));
+
+        high_scores.save(&path).unwrap();
+        let loaded = HighScores::load(&path).unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(loaded, high_scores);
+        assert!(HighScores::load(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn corrupt_files_are_backed_up_without_replacing_older_backups() {
+        let dir = env::temp_dir().join(format!("asteroids-highscores-backup-{}", std::process::id()));
+        let path = dir.join("scores.txt");
+        fs::create_dir_all(&dir).unwrap();
+
+        fs::write(&path, "first\n").unwrap();
+        let (first, first_backup) = HighScores::load_or_back_up(&path).unwrap();
+        fs::write(&path, "second\n").unwrap();
+        let (_, second_backup) = HighScores::load_or_back_up(&path).unwrap();
+        let (_, no_backup) = HighScores::load_or_back_up(&path).unwrap();
+        let contents = [dir.join("scores.txt.bak"), dir.join("scores.txt.bak.2")].map(fs::read_to_string);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(first.is_empty());
+        assert_eq!(first_backup, Some(dir.join("scores.txt.bak")));
+        assert_eq!(second_backup, Some(dir.join("scores.txt.bak.2")));
+        assert_eq!(no_backup, None);
+        assert_eq!(contents.map(Result::unwrap), ["first\n", "second\n"]);
+    }
+
+    #[test]
+    fn unreadable_files_are_left_alone() {
+        let dir = env::temp_dir().join(format!("asteroids-highscores-unreadable-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let result = HighScores::load_or_back_up(&dir);
+        let still_there = dir.is_dir();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(result.is_err());
+        assert!(still_there);
+    }
+
+    #[test]
+    fn malformed_entries_are_rejected() {
+        assert!(parse_entry("AB 100 2026-10-18 1 0").is_err());
+        assert!(parse_entry("ABC lots 2026-10-18 1 0").is_err());
+        assert_eq!(parse_entry("ABC 100 2026-10-18 1 0").unwrap().score, 100);
+    }
+
+    #[test]
+    fn days_convert_to_calendar_dates() {
+        assert_eq!(date_from_days(0), "1970-01-01");
+        assert_eq!(date_from_days(11_016), "2000-02-29");
+        assert_eq!(date_from_days(20_744), "2026-10-18");
+    }
+
+    #[test]
+    fn initials_cycle_and_wrap() {
+        let mut initials = InitialsEntry::new();
+        initials.previous_letter();
+        initials.right();
+        initials.next_letter();
+        initials.next_letter();
+        initials.right();
+        assert!(initials.is_on_last_letter());
+        initials.left();
+        initials.left();
+        initials.left();
+
+        assert_eq!(initials.cursor(), 0);
+        assert_eq!(initials.initials(), "ZCA");
+        for _ in 0..4 {
+            initials.right();
+        }
+        assert_eq!(initials.cursor(), 2);
+    }
+}
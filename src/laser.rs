@@ -1,29 +1,110 @@
-use crate::core::{colour::RGB, renderer::Renderer};
+use std::time::Duration;
+
+use crate::{core::{colour::RGB, renderer::Renderer}, geometry::Vec2, utils};
+
+pub const DEFAULT_MAX_DISTANCE: f64 = 560.0;
+pub const DEFAULT_TTL: Duration = Duration::from_millis(1500);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Faction {
+    Player,
+    Enemy,
+}
+
+impl Faction {
+    pub fn colour(self) -> RGB {
+        match self {
+            Faction::Player => RGB::WHITE,
+            Faction::Enemy => RGB::RED,
+        }
+    }
+
+    // No friendly fire: lasers only hurt the other side. Asteroids are fair game for both.
+    pub fn is_hostile_to(self, other: Faction) -> bool {
+        self != other
+    }
+}
 
 #[derive(Debug)]
 pub struct Laser {
     pub x: f64,
     pub y: f64,
     pub angle: f64,
+    pub owner: Faction,
+    pub max_distance: f64,
+    pub ttl: Duration,
+    prev_x: f64,
+    prev_y: f64,
+    wrap_offset: Vec2,
     speed: f64,
+    distance_travelled: f64,
+    age: Duration,
 }
 
 impl Laser {
-    pub fn new(x: f64, y: f64, angle: f64) -> Self {
-        Laser { x, y, angle, speed: 0.075 }
+    pub fn new(x: f64, y: f64, angle: f64, owner: Faction) -> Self {
+        Laser {
+            x,
+            y,
+            angle,
+            owner,
+            max_distance: DEFAULT_MAX_DISTANCE,
+            ttl: DEFAULT_TTL,
+            prev_x: x,
+            prev_y: y,
+            wrap_offset: Vec2::default(),
+            speed: 375.0,
+            distance_travelled: 0.0,
+            age: Duration::ZERO,
+        }
     }
 
-    pub fn update(&mut self) {
-        self.x += self.speed * self.angle.cos();
-        self.y += self.speed * self.angle.sin();
+    pub fn max_distance(mut self, max_distance: f64) -> Self {
+        self.max_distance = max_distance;
+        self
     }
 
-    pub fn draw(&self, renderer: &mut impl Renderer, colour: RGB) -> Result<(), String> {
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn update(&mut self, dt: f64, screen_width: u32, screen_height: u32) {
+        self.prev_x = self.x;
+        self.prev_y = self.y;
+        self.x += self.speed * self.angle.cos() * dt;
+        self.y += self.speed * self.angle.sin() * dt;
+        self.distance_travelled += self.speed * dt;
+        self.age = self.age.saturating_add(Duration::from_secs_f64(dt));
+        self.ensure_laser_is_on_screen(screen_width, screen_height);
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.distance_travelled >= self.max_distance || self.age >= self.ttl
+    }
+
+    pub fn segment(&self) -> (Vec2, Vec2) {
+        (Vec2::new(self.prev_x, self.prev_y), Vec2::new(self.x, self.y))
+    }
+
+    /// Everything swept this step. A step that wrapped also yields the
+    /// unwrapped segment, which covers the part before the edge.
+    pub fn segments(&self) -> impl Iterator<Item = (Vec2, Vec2)> {
+        let (start, end) = self.segment();
+        let before_wrap = (self.wrap_offset != Vec2::default())
+            .then(|| (start - self.wrap_offset, end - self.wrap_offset));
+        std::iter::once((start, end)).chain(before_wrap)
+    }
+
+    pub fn draw(&self, renderer: &mut impl Renderer, alpha: f64) -> Result<(), String> {
+        let colour = self.owner.colour();
         let dot_size = 3;
+        let x = utils::lerp(self.prev_x, self.x, alpha);
+        let y = utils::lerp(self.prev_y, self.y, alpha);
 
         renderer.draw_rect(
-            (self.x - dot_size as f64 / 2.0) as i32,
-            (self.y - dot_size as f64 / 2.0) as i32,
+            (x - dot_size as f64 / 2.0) as i32,
+            (y - dot_size as f64 / 2.0) as i32,
             dot_size as u32,
             dot_size as u32,
             colour,
@@ -31,4 +112,64 @@ impl Laser {
 
         Ok(())
     }
+
+    fn ensure_laser_is_on_screen(&mut self, screen_width: u32, screen_height: u32) {
+        let (x, y) = (self.x, self.y);
+        if self.x < 0.0 { self.x += screen_width as f64 }
+        else if self.x > screen_width as f64 { self.x -= screen_width as f64 }
+        if self.y < 0.0 { self.y += screen_height as f64 }
+        else if self.y > screen_height as f64 { self.y -= screen_height as f64 }
+        self.wrap_offset = Vec2::new(self.x - x, self.y - y);
+        self.prev_x += self.wrap_offset.x;
+        self.prev_y += self.wrap_offset.y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn laser_wraps_around_screen() {
+        let mut laser = Laser::new(795.0, 300.0, 0.0, Faction::Player);
+
+        laser.update(0.1, 800, 600);
+
+        assert!((laser.x - 32.5).abs() < 1e-9);
+        let (start, end) = laser.segment();
+        assert!((end.x - start.x - 37.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn wrapping_step_sweeps_both_sides_of_the_edge() {
+        let mut laser = Laser::new(795.0, 300.0, 0.0, Faction::Player);
+        laser.update(0.1, 800, 600);
+
+        let segments: Vec<_> = laser.segments().collect();
+        assert_eq!(segments.len(), 2);
+        let (start, end) = segments[1];
+        assert!((start.x - 795.0).abs() < 1e-9);
+        assert!((end.x - 832.5).abs() < 1e-9);
+
+        laser.update(0.01, 800, 600);
+        assert_eq!(laser.segments().count(), 1);
+    }
+
+    #[test]
+    fn laser_expires_after_max_distance() {
+        let mut laser = Laser::new(0.0, 300.0, 0.0, Faction::Player).max_distance(100.0);
+        laser.update(0.2, 800, 600);
+        assert!(!laser.is_expired());
+        laser.update(0.1, 800, 600);
+        assert!(laser.is_expired());
+    }
+
+    #[test]
+    fn laser_expires_after_ttl() {
+        let mut laser = Laser::new(0.0, 300.0, 0.0, Faction::Enemy).ttl(Duration::from_millis(100));
+        laser.update(0.05, 800, 600);
+        assert!(!laser.is_expired());
+        laser.update(0.06, 800, 600);
+        assert!(laser.is_expired());
+    }
 }
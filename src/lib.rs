@@ -0,0 +1,16 @@
+pub mod player;
+pub mod laser;
+pub mod asteroid;
+pub mod game_state;
+pub mod utils;
+pub mod core;
+pub mod geometry;
+pub mod broadphase;
+pub mod scoring;
+pub mod wave;
+pub mod saucer;
+pub mod particles;
+pub mod settings;
+pub mod highscores;
+pub mod sound;
+pub mod menu;
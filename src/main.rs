@@ -1,16 +1,28 @@
-mod player;
-mod laser;
-mod asteroid;
-mod game_state;
-mod utils;
-mod core;
+use std::time::{Duration, Instant};
 
-use core::{colour::RGB, input::SdlController, renderer::{Renderer, SdlRenderer}};
+use asteroids::core::{
+    colour::RGB,
+    input::{menu_command, InputController, ScriptedController, SdlController},
+    renderer::{Renderer, SdlRenderer},
+    replay::{self, Recording},
+};
+use asteroids::game_state::{GameState, State, TIMESTEP};
+use asteroids::highscores::HighScores;
+use asteroids::settings::ShipStyle;
+use sdl2::{audio::AudioSpecDesired, controller::GameController, event::Event};
 
-use sdl2::{event::Event, keyboard::Keycode};
-use game_state::{GameState, State};
+const MAX_FRAME_TIME: Duration = Duration::from_millis(250);
+
+struct Args {
+    seed: Option<u64>,
+    record: Option<String>,
+    outline_ship: bool,
+}
 
 fn main() -> Result<(), String> {
+    let args = parse_args()?;
+    let seed = args.seed.unwrap_or_else(rand::random);
+
     let ttf_context = sdl2::ttf::init().map_err(|e| e.to_string())?;
     let font_path = "./src/assets/font/HomeVideoBold-R90Dv.ttf";
     let font: sdl2::ttf::Font<'_, '_> = ttf_context.load_font(font_path, 32)?;
@@ -20,6 +32,19 @@ fn main() -> Result<(), String> {
 
     let sdl_context = sdl2::init()?;
     let video_subsystem = sdl_context.video()?;
+    let game_controller_subsystem = sdl_context.game_controller()?;
+    let mut gamepads: Vec<GameController> = (0..game_controller_subsystem.num_joysticks()?)
+        .filter(|&index| game_controller_subsystem.is_game_controller(index))
+        .filter_map(|index| game_controller_subsystem.open(index).ok())
+        .collect();
+    let audio_spec = AudioSpecDesired { freq: Some(44_100), channels: Some(1), samples: None };
+    let audio = sdl_context.audio()
+        .and_then(|audio_subsystem| audio_subsystem.open_queue::<f32, _>(None, &audio_spec))
+        .map_err(|e| eprintln!("audio disabled: {}", e))
+        .ok();
+    if let Some(queue) = &audio {
+        queue.resume();
+    }
     let window = video_subsystem.window("Asteroids", screen_width, screen_height)
         .build()
         .unwrap();
@@ -31,49 +56,133 @@ fn main() -> Result<(), String> {
     let mut renderer = SdlRenderer::new(&mut canvas, &font, screen_width, screen_height);
 
     let mut event_queue = sdl_context.event_pump().unwrap();
-    let mut game_state = GameState::new(screen_width, screen_height);
-
+    let mut game_state = GameState::new(screen_width, screen_height, seed);
+    if args.outline_ship {
+        game_state.settings.ship_style = ShipStyle::Outline;
+    }
+    let mut high_score_path = HighScores::default_path();
+    if let Some(path) = high_score_path.clone() {
+        match HighScores::load_or_back_up(&path) {
+            Ok((high_scores, backup)) => {
+                if let Some(backup) = backup {
+                    eprintln!("moved unreadable high scores in {} to {}", path.display(), backup.display());
+                }
+                game_state.high_scores = high_scores;
+            }
+            Err(e) => {
+                eprintln!("high scores in {} will not be loaded or saved: {}", path.display(), e);
+                high_score_path = None;
+            }
+        }
+    }
+    game_state.next_seed = args.seed;
+    game_state.enter_title();
+    let mut previous_time = Instant::now();
+    let mut accumulator = Duration::ZERO;
+    let mut recording = Recording::new(seed);
+    let mut games = 0;
 
     while game_state.running {
 
+        let previous_state = game_state.state;
         for event in event_queue.poll_iter() {
             match event {
                 Event::Quit {..} => {
                     game_state.running = false;
                 }
-                Event::KeyDown { keycode: Some(Keycode::Escape), .. } => {
-                    game_state.toggle_paused();
+                Event::ControllerDeviceAdded { which, .. } => {
+                    if let Ok(gamepad) = game_controller_subsystem.open(which) {
+                        gamepads.push(gamepad);
+                    }
                 }
-                Event::KeyDown { keycode: Some(Keycode::Return), .. } => {
-                    if game_state.state == State::GameOver {
-                        game_state.reset();
-                        game_state.state = State::Playing;
+                _ => {
+                    if let Some(command) = menu_command(&event) {
+                        game_state.handle_menu(command);
                     }
                 }
-                _ => {}
             }
         }
+        if previous_state == State::EnterInitials && game_state.state != State::EnterInitials {
+            if let Some(path) = &high_score_path {
+                if let Err(e) = game_state.high_scores.save(path) {
+                    eprintln!("failed to save high scores to {}: {}", path.display(), e);
+                }
+            }
+        }
+        if game_state.state == State::Playing && !matches!(previous_state, State::Playing | State::Paused) {
+            recording = Recording::new(game_state.seed);
+            games += 1;
+        }
 
-        renderer.set_colour(black);
-        renderer.clear();
+        let now = Instant::now();
+        let frame_time = (now - previous_time).min(MAX_FRAME_TIME);
+        previous_time = now;
 
-        match game_state.state {
-            State::Playing => {
-                game_state.update(&SdlController::new(&event_queue));
-                game_state.draw(&mut renderer)?;
+        if game_state.is_simulating() {
+            accumulator += frame_time;
+            let controller = SdlController::new(&event_queue);
+            let playing = game_state.state == State::Playing;
+            while accumulator >= TIMESTEP && game_state.is_simulating() {
+                let commands = controller.poll();
+                if playing {
+                    recording.record(&commands);
+                }
+                game_state.update(&ScriptedController::new(commands), TIMESTEP.as_secs_f64());
+                accumulator -= TIMESTEP;
             }
-            State::Paused => {
-                game_state.draw(&mut renderer)?;
+            if playing {
+                recording.final_score = Some(game_state.player.score);
             }
-            State::GameOver => {
-                let renderer_ref = &mut renderer;
-                renderer_ref.draw_game_over_screen(game_state.player.score)?;
+            if playing && matches!(game_state.state, State::EnterInitials | State::GameOver) {
+                save_recording(&args, &recording, games)?;
+                recording = Recording::new(game_state.seed);
             }
         }
+        for sound in game_state.take_sounds() {
+            if let Some(queue) = &audio {
+                if let Err(e) = queue.queue_audio(&sound.samples(queue.spec().freq as u32)) {
+                    eprintln!("failed to play {:?}: {}", sound, e);
+                }
+            }
+        }
+        let alpha = accumulator.as_secs_f64() / TIMESTEP.as_secs_f64();
 
+        renderer.set_colour(black);
+        renderer.clear();
+        game_state.draw(&mut renderer, alpha)?;
         renderer.present();
 
     }
 
+    if !recording.is_empty() {
+        save_recording(&args, &recording, games)?;
+    }
+
     Ok(())
 }
+
+fn parse_args() -> Result<Args, String> {
+    let mut parsed = Args { seed: None, record: None, outline_ship: false };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let value = args.next().ok_or("--seed requires a value")?;
+                parsed.seed = Some(value.parse::<u64>().map_err(|e| format!("invalid seed '{}': {}", value, e))?);
+            }
+            "--record" => {
+                parsed.record = Some(args.next().ok_or("--record requires a path")?);
+            }
+            "--outline-ship" => parsed.outline_ship = true,
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+    Ok(parsed)
+}
+
+fn save_recording(args: &Args, recording: &Recording, game: usize) -> Result<(), String> {
+    match &args.record {
+        Some(path) => recording.save(replay::numbered_path(path, game)),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,73 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuItem {
+    Play,
+    Options,
+    HighScores,
+    Quit,
+    ShipStyle,
+    Back,
+    Resume,
+    QuitToTitle,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Menu {
+    items: Vec<MenuItem>,
+    selected: usize,
+}
+
+impl Menu {
+    pub fn new(items: &[MenuItem]) -> Self {
+        Menu { items: items.to_vec(), selected: 0 }
+    }
+
+    pub fn title() -> Self {
+        Menu::new(&[MenuItem::Play, MenuItem::Options, MenuItem::HighScores, MenuItem::Quit])
+    }
+
+    pub fn options() -> Self {
+        Menu::new(&[MenuItem::ShipStyle, MenuItem::Back])
+    }
+
+    pub fn pause() -> Self {
+        Menu::new(&[MenuItem::Resume, MenuItem::QuitToTitle])
+    }
+
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected(&self) -> MenuItem {
+        self.items[self.selected]
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % self.items.len();
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_wraps_both_ways() {
+        let mut menu = Menu::title();
+        assert_eq!(menu.selected(), MenuItem::Play);
+
+        menu.previous();
+        assert_eq!(menu.selected(), MenuItem::Quit);
+
+        menu.next();
+        menu.next();
+        assert_eq!(menu.selected(), MenuItem::Options);
+    }
+}
@@ -0,0 +1,206 @@
+use std::{f64::consts::PI, time::Duration};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{core::{colour::RGB, renderer::{Point, Renderer}}, geometry::{Polygon, Vec2}, utils};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParticleKind {
+    Debris { length: f64, angle: f64, spin: f64 },
+    Spark,
+    Dot,
+}
+
+#[derive(Clone, Debug)]
+pub struct Particle {
+    pub kind: ParticleKind,
+    position: Vec2,
+    prev_position: Vec2,
+    velocity: Vec2,
+    age: Duration,
+    lifetime: Duration,
+}
+
+impl Particle {
+    pub fn new(kind: ParticleKind, position: Vec2, velocity: Vec2, lifetime: Duration) -> Self {
+        Particle { kind, position, prev_position: position, velocity, age: Duration::ZERO, lifetime }
+    }
+
+    pub fn update(&mut self, dt: f64) {
+        self.prev_position = self.position;
+        self.position += self.velocity * dt;
+        self.age = self.age.saturating_add(Duration::from_secs_f64(dt));
+        if let ParticleKind::Debris { angle, spin, .. } = &mut self.kind {
+            *angle += *spin * dt;
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.age >= self.lifetime
+    }
+
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    pub fn draw(&self, renderer: &mut impl Renderer, colour: RGB, alpha: f64) -> Result<(), String> {
+        let position = Vec2::new(
+            utils::lerp(self.prev_position.x, self.position.x, alpha),
+            utils::lerp(self.prev_position.y, self.position.y, alpha),
+        );
+        let colour = self.faded(colour);
+
+        match self.kind {
+            ParticleKind::Debris { length, angle, .. } => {
+                let half = Vec2::from_angle(angle, length / 2.0);
+                renderer.draw_lines(&[(position - half).into(), (position + half).into()], colour)
+            }
+            ParticleKind::Spark => {
+                let tail = self.velocity * -0.03;
+                renderer.draw_lines(&[Point::from(position), Point::from(position + tail)], colour)
+            }
+            ParticleKind::Dot => {
+                renderer.draw_rect(position.x as i32, position.y as i32, 2, 2, colour);
+                Ok(())
+            }
+        }
+    }
+
+    fn faded(&self, colour: RGB) -> RGB {
+        let remaining = 1.0 - (self.age.as_secs_f64() / self.lifetime.as_secs_f64()).clamp(0.0, 1.0);
+        RGB {
+            r: (colour.r as f64 * remaining) as u8,
+            g: (colour.g as f64 * remaining) as u8,
+            b: (colour.b as f64 * remaining) as u8,
+        }
+    }
+}
+
+// Particles are cosmetic, so they draw from their own RNG and never shift the
+// gameplay rolls that a seed or replay depends on.
+pub struct Particles {
+    particles: Vec<Particle>,
+    rng: StdRng,
+}
+
+impl Particles {
+    pub fn new(seed: u64) -> Self {
+        Particles { particles: Vec::new(), rng: StdRng::seed_from_u64(seed) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Particle> {
+        self.particles.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.particles.clear();
+    }
+
+    pub fn reset(&mut self, seed: u64) {
+        self.clear();
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    pub fn push(&mut self, particle: Particle) {
+        self.particles.push(particle);
+    }
+
+    pub fn update(&mut self, dt: f64) {
+        for particle in self.particles.iter_mut() {
+            particle.update(dt);
+        }
+        self.particles.retain(|particle| !particle.is_expired());
+    }
+
+    pub fn draw(&self, renderer: &mut impl Renderer, colour: RGB, alpha: f64) -> Result<(), String> {
+        for particle in &self.particles {
+            particle.draw(renderer, colour, alpha)?;
+        }
+        Ok(())
+    }
+
+    pub fn spawn_debris(&mut self, outline: &Polygon) {
+        let rng = &mut self.rng;
+        let (min, max) = outline.bounds();
+        let centre = Vec2::new((min.x + max.x) / 2.0, (min.y + max.y) / 2.0);
+        for (start, end) in outline.edges() {
+            let midpoint = Vec2::new((start.x + end.x) / 2.0, (start.y + end.y) / 2.0);
+            let edge = end - start;
+            let outward = midpoint - centre;
+            let heading = outward.y.atan2(outward.x) + rng.gen_range(-0.4..0.4);
+            let kind = ParticleKind::Debris {
+                length: edge.length(),
+                angle: edge.y.atan2(edge.x),
+                spin: rng.gen_range(-4.0..4.0),
+            };
+            let velocity = Vec2::from_angle(heading, rng.gen_range(20.0..60.0));
+            let lifetime = Duration::from_secs_f64(rng.gen_range(1.0..2.0));
+            self.particles.push(Particle::new(kind, midpoint, velocity, lifetime));
+        }
+    }
+
+    pub fn spawn_sparks(&mut self, at: Vec2, count: usize) {
+        let rng = &mut self.rng;
+        for _ in 0..count {
+            let velocity = Vec2::from_angle(rng.gen_range(0.0..2.0 * PI), rng.gen_range(80.0..200.0));
+            let lifetime = Duration::from_secs_f64(rng.gen_range(0.15..0.35));
+            self.particles.push(Particle::new(ParticleKind::Spark, at, velocity, lifetime));
+        }
+    }
+
+    pub fn spawn_dots(&mut self, at: Vec2, count: usize) {
+        let rng = &mut self.rng;
+        for _ in 0..count {
+            let velocity = Vec2::from_angle(rng.gen_range(0.0..2.0 * PI), rng.gen_range(20.0..90.0));
+            let lifetime = Duration::from_secs_f64(rng.gen_range(0.4..0.9));
+            self.particles.push(Particle::new(ParticleKind::Dot, at, velocity, lifetime));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn particle_moves_then_expires() {
+        let mut particles = Particles::new(0);
+        particles.push(Particle::new(ParticleKind::Dot, Vec2::ZERO, Vec2::new(10.0, 0.0), Duration::from_millis(500)));
+
+        particles.update(0.25);
+        assert_eq!(particles.len(), 1);
+        assert!((particles.iter().next().unwrap().position().x - 2.5).abs() < 1e-9);
+
+        particles.update(0.25);
+        assert!(particles.is_empty());
+    }
+
+    #[test]
+    fn debris_has_one_segment_per_edge() {
+        let mut particles = Particles::new(0);
+        let ship = utils::get_vertices((100.0, 100.0), 0.0, 20.0);
+
+        particles.spawn_debris(&ship);
+
+        assert_eq!(particles.len(), 3);
+        assert!(particles.iter().all(|p| matches!(p.kind, ParticleKind::Debris { .. })));
+    }
+
+    #[test]
+    fn particles_fade_over_lifetime() {
+        let mut particle = Particle::new(ParticleKind::Spark, Vec2::ZERO, Vec2::ZERO, Duration::from_secs(1));
+        assert_eq!(particle.faded(RGB::WHITE), RGB::WHITE);
+
+        particle.update(0.5);
+
+        assert_eq!(particle.faded(RGB::WHITE).r, 127);
+    }
+}
@@ -1,13 +1,23 @@
-use std::{f64::consts::PI, time::{Duration, Instant}};
+use std::{f64::consts::PI, time::Duration};
 
-use crate::{core::{colour::RGB, input::{Command, InputController}, renderer::{Renderer, Point}}, laser::Laser, utils};
+use crate::{core::{colour::RGB, input::{Command, InputController}, renderer::{Point, Renderer}}, geometry::{Polygon, Vec2}, laser::{Faction, Laser}, scoring::BonusLifeRule, settings::ShipStyle, utils};
+
+const MAX_LIFE_ICONS: u8 = 5;
+const BONUS_LIFE_CUE: Duration = Duration::from_secs(2);
+const SHIELD_RADIUS: f64 = 28.0;
+const SPAWN_ZONE_RADIUS: f64 = 40.0;
 
 pub struct Player {
     pub angle: f64,
-    pub vertices: Vec<Point>,
+    pub vertices: Polygon,
     pub score: u32,
+    pub bonus_life: BonusLifeRule,
+    pub shield_energy: f64,
     x: f64,
     y: f64,
+    prev_x: f64,
+    prev_y: f64,
+    prev_angle: f64,
     velocity_x: f64,
     velocity_y: f64,
     rotation_speed: f64,
@@ -15,8 +25,14 @@ pub struct Player {
     max_velocity: f64,
     deceleration: f64,
     lives: u8,
-    timer: Instant,
+    timer: Duration,
     invulnrable: bool,
+    respawning: bool,
+    thrusting: bool,
+    bonus_life_timer: Option<Duration>,
+    shielded: bool,
+    shield_drain: f64,
+    shield_recharge: f64,
 }
 
 impl Player {
@@ -24,37 +40,65 @@ impl Player {
         Self {
             x,
             y,
-            vertices: Vec::new(),
+            prev_x: x,
+            prev_y: y,
+            prev_angle: -PI/2.0,
+            vertices: Polygon::default(),
             angle: -PI/2.0,
             velocity_x: 0.0,
             velocity_y: 0.0,
-            rotation_speed: 0.001,
-            acceleration: 0.000025,
-            max_velocity: 0.065,
-            deceleration: 0.000005,
+            rotation_speed: 5.0,
+            acceleration: 625.0,
+            max_velocity: 325.0,
+            deceleration: 125.0,
             score: 0,
+            bonus_life: BonusLifeRule::default(),
             lives: 3,
-            timer: Instant::now(),
+            timer: Duration::ZERO,
             invulnrable: false,
+            respawning: false,
+            thrusting: false,
+            bonus_life_timer: None,
+            shield_energy: 1.0,
+            shielded: false,
+            shield_drain: 0.5,
+            shield_recharge: 0.2,
         }
     }
 
-    pub fn update(&mut self, controller: &impl InputController, screen_width: u32, screen_height: u32) {
+    pub fn update(&mut self, controller: &impl InputController, dt: f64, screen_width: u32, screen_height: u32) {
+        self.prev_x = self.x;
+        self.prev_y = self.y;
+        self.prev_angle = self.angle;
+
+        if let Some(timer) = self.bonus_life_timer {
+            let timer = timer + Duration::from_secs_f64(dt);
+            self.bonus_life_timer = (timer < BONUS_LIFE_CUE).then_some(timer);
+        }
+
+        self.thrusting = false;
+        if self.respawning {
+            self.timer += Duration::from_secs_f64(dt);
+            return;
+        }
+
         for cmd in controller.poll() {
             match cmd {
-                Command::RotateLeft => self.angle -= self.rotation_speed,
-                Command::RotateRight => self.angle += self.rotation_speed,
+                Command::RotateLeft => self.angle -= self.rotation_speed * dt,
+                Command::RotateRight => self.angle += self.rotation_speed * dt,
                 Command::Accelerate => {
-                    self.velocity_x += self.acceleration * self.angle.cos();
-                    self.velocity_y += self.acceleration * self.angle.sin();
+                    self.thrusting = true;
+                    self.velocity_x += self.acceleration * self.angle.cos() * dt;
+                    self.velocity_y += self.acceleration * self.angle.sin() * dt;
                 },
                 _ => {},
             }
         }
 
         if !controller.poll().contains(&Command::Accelerate) {
-            self.velocity_x -= self.deceleration * self.velocity_x.signum();
-            self.velocity_y -= self.deceleration * self.velocity_y.signum();
+            let deceleration = self.deceleration * dt;
+            self.velocity_x -= deceleration.min(self.velocity_x.abs()) * self.velocity_x.signum();
+            self.velocity_y -= deceleration.min(self.velocity_y.abs()) * self.velocity_y.signum();
         }
 
         let current_velocity: f64 = (self.velocity_x.powi(2) + self.velocity_y.powi(2)).sqrt();
@@ -64,26 +108,82 @@ impl Player {
             self.velocity_y *= scale;
         }
 
-        if self.timer.elapsed() >= Duration::from_secs(3) {
+        self.update_shield(controller.poll().contains(&Command::Shield), dt);
+
+        self.timer += Duration::from_secs_f64(dt);
+        if self.timer >= Duration::from_secs(3) {
             self.invulnrable = false;
         }
 
-        self.move_player();
+        self.move_player(dt);
         self.vertices = utils::get_vertices((self.x, self.y), self.angle, 20.0);
         self.ensure_player_is_on_screen(screen_width, screen_height);
 
     }
 
-    pub fn draw(&self, renderer: &mut impl Renderer, color: RGB) -> Result<(), String> {
-            if self.invulnrable && self.timer.elapsed().as_millis() / 150 % 2 == 0 {
+    pub fn draw(&self, renderer: &mut impl Renderer, color: RGB, alpha: f64, style: ShipStyle) -> Result<(), String> {
+            let x = utils::lerp(self.prev_x, self.x, alpha);
+            let y = utils::lerp(self.prev_y, self.y, alpha);
+            if self.respawning {
+                let pulse = (self.timer.as_secs_f64() * 3.0 * PI).sin();
+                let radius = SPAWN_ZONE_RADIUS * (0.85 + 0.15 * pulse);
+                return renderer.draw_circle(Vec2::new(x, y).into(), radius as i32, color);
+            }
+            if self.shielded {
+                renderer.draw_circle(Vec2::new(x, y).into(), SHIELD_RADIUS as i32, color)?;
+            }
+            if self.invulnrable && (self.timer.as_millis() / 150).is_multiple_of(2) {
                 return Ok(());
             }
-            renderer.draw_vertices(&self.vertices, color)?;
+            let angle = utils::lerp(self.prev_angle, self.angle, alpha);
+            let vertices = utils::get_vertices((x, y), angle, 20.0);
+            if self.thrusting {
+                self.draw_thrust_flame(renderer, color, Vec2::new(x, y), angle)?;
+            }
+            match style {
+                ShipStyle::Filled => renderer.draw_vertices(&vertices.to_points(), color)?,
+                ShipStyle::Outline => Self::draw_outline(renderer, color, &vertices)?,
+            }
         Ok(())
     }
 
-    pub fn increment_score(&mut self) {
-        self.score += 1;
+    fn draw_thrust_flame(&self, renderer: &mut impl Renderer, color: RGB, centre: Vec2, angle: f64) -> Result<(), String> {
+        let scale = 20.0;
+        let flicker = if (self.timer.as_millis() / 50).is_multiple_of(2) { 0.9 } else { 0.6 };
+        let heading = Vec2::from_angle(angle, 1.0);
+        let side = heading.rotated(PI / 2.0);
+        let base = centre - heading * (0.25 * scale);
+        let flame = [
+            base + side * (0.2 * scale),
+            centre - heading * (flicker * scale),
+            base - side * (0.2 * scale),
+        ];
+        let points: Vec<Point> = flame.iter().copied().map(Into::into).collect();
+        renderer.draw_lines(&points, color)
+    }
+
+    fn draw_outline(renderer: &mut impl Renderer, color: RGB, vertices: &Polygon) -> Result<(), String> {
+        let [nose, left, right] = [vertices.vertices[0], vertices.vertices[1], vertices.vertices[2]];
+        let sides: Vec<Point> = [left, nose, right].iter().copied().map(Into::into).collect();
+        renderer.draw_lines(&sides, color)?;
+        let crossbar = [nose + (left - nose) * 0.75, nose + (right - nose) * 0.75];
+        let crossbar: Vec<Point> = crossbar.iter().copied().map(Into::into).collect();
+        renderer.draw_lines(&crossbar, color)
+    }
+
+    /// Returns `true` when the points earned a bonus life.
+    pub fn add_score(&mut self, points: u32) -> bool {
+        let score = self.score.saturating_add(points);
+        let earned = self.bonus_life.lives_earned(self.score, score);
+        self.score = score;
+
+        if earned > 0 && self.lives < self.bonus_life.max_lives {
+            let lives = (self.lives as u32 + earned).min(self.bonus_life.max_lives as u32);
+            self.lives = lives as u8;
+            self.bonus_life_timer = Some(Duration::ZERO);
+            return true;
+        }
+        false
     }
 
     pub fn draw_score(&self, renderer: &mut impl Renderer, color: RGB) -> Result<(), String> {
@@ -100,55 +200,230 @@ impl Player {
         let scale = 15.0;
         let offset = 35.0;
         let spacing = 30.0;
+
+        if let Some(timer) = self.bonus_life_timer {
+            if (timer.as_millis() / 200).is_multiple_of(2) {
+                renderer.draw_text("EXTRA LIFE", color, (screen_width as i32 - 240, 60))?;
+            }
+        }
+
+        if self.lives > MAX_LIFE_ICONS {
+            let x = screen_width as f64 - offset - 3.0 * spacing;
+            let vertices = utils::get_vertices((x, offset), -PI/2.0, scale);
+            renderer.draw_vertices(&vertices.to_points(), color)?;
+            let text = format!("x {}", self.lives);
+            return renderer.draw_text(&text, color, ((x + spacing) as i32, 15));
+        }
+
         let start_x: f64 = screen_width as f64 - offset - spacing * (self.lives - 1) as f64;
         for i in 0..self.lives {
             let x: f64 = start_x + i as f64 * spacing;
             let vertices = utils::get_vertices((x, offset), -PI/2.0, scale);
-            renderer.draw_vertices(&vertices, color)?;
+            renderer.draw_vertices(&vertices.to_points(), color)?;
         }
         Ok(())
     }
 
+    pub fn draw_shield_meter(&self, renderer: &mut impl Renderer, color: RGB) -> Result<(), String> {
+        let (x, y, width, height) = (25, 66, 120, 8);
+        let outline = [(x, y), (x + width, y), (x + width, y + height), (x, y + height), (x, y)];
+        let outline: Vec<Point> = outline.iter().map(|&(x, y)| Point::new(x, y)).collect();
+        renderer.draw_lines(&outline, color)?;
+        let filled = (self.shield_energy * width as f64) as u32;
+        renderer.draw_rect(x, y, filled, height as u32, color);
+        Ok(())
+    }
+
     pub fn hit(&mut self, screen_width: u32, screen_height: u32) {
-        if !self.invulnrable {
+        if !self.is_invulnerable() {
             self.x = (screen_width/2) as f64;
             self.y = (screen_height/2) as f64;
+            self.prev_x = self.x;
+            self.prev_y = self.y;
             self.lives -= 1;
             self.velocity_x = 0.0;
             self.velocity_y = 0.0;
-            self.invulnrable = true;
-            self.timer = Instant::now();
+            self.shielded = false;
+            self.respawning = true;
+            self.timer = Duration::ZERO;
+            self.vertices = utils::get_vertices((self.x, self.y), self.angle, 20.0);
         }
     }
 
-    pub fn is_dead(&self) -> bool {
-        self.lives == 0
+    pub fn respawn(&mut self) {
+        self.respawning = false;
+        self.invulnrable = true;
+        self.timer = Duration::ZERO;
     }
 
-    pub fn reset(&mut self, screen_width: u32, screen_height: u32) {
-        self.score = 0;
-        self.lives = 3;
-        self.x = (screen_width/2) as f64;
-        self.y = (screen_height/2) as f64;
+    pub fn is_respawning(&self) -> bool {
+        self.respawning
+    }
+
+    pub fn spawn_zone_radius(&self) -> f64 {
+        SPAWN_ZONE_RADIUS
+    }
+
+    // Jumping forfeits any remaining respawn protection and drops the shield.
+    pub fn hyperspace(&mut self, x: f64, y: f64) {
+        self.x = x;
+        self.y = y;
+        self.prev_x = x;
+        self.prev_y = y;
         self.velocity_x = 0.0;
         self.velocity_y = 0.0;
         self.invulnrable = false;
+        self.shielded = false;
+        self.vertices = utils::get_vertices((self.x, self.y), self.angle, 20.0);
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnrable || self.shielded || self.respawning
+    }
+
+    pub fn is_shielded(&self) -> bool {
+        self.shielded
+    }
+
+    pub fn shield_radius(&self) -> f64 {
+        SHIELD_RADIUS
+    }
+
+    pub fn position(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    pub fn lives(&self) -> u8 {
+        self.lives
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.lives == 0
+    }
+
+    // Everything but the bonus-life rule goes back to how `new` left it, so a
+    // game started after attract mode replays from a fresh player.
+    pub fn reset(&mut self, screen_width: u32, screen_height: u32) {
+        let bonus_life = self.bonus_life.clone();
+        *self = Player::new(screen_width as f64 / 2.0, screen_height as f64 / 2.0);
+        self.bonus_life = bonus_life;
     }
 
     pub fn fire(&self) -> Laser {
-        Laser::new(self.x, self.y, self.angle)
+        Laser::new(self.x, self.y, self.angle, Faction::Player)
+    }
+
+    fn update_shield(&mut self, held: bool, dt: f64) {
+        self.shielded = held && self.shield_energy > 0.0;
+        if held {
+            self.shield_energy = (self.shield_energy - self.shield_drain * dt).max(0.0);
+        } else {
+            self.shield_energy = (self.shield_energy + self.shield_recharge * dt).min(1.0);
+        }
     }
 
-    fn move_player(&mut self) {
-        self.x += self.velocity_x - self.deceleration;
-        self.y += self.velocity_y - self.deceleration;
+    fn move_player(&mut self, dt: f64) {
+        self.x += self.velocity_x * dt;
+        self.y += self.velocity_y * dt;
     }
 
     fn ensure_player_is_on_screen(&mut self, screen_width: u32, screen_height: u32) {
+        let (x, y) = (self.x, self.y);
         if self.x < 0.0 { self.x = screen_width as f64 }
         else if self.x > screen_width as f64 { self.x = 0.0 }
         if self.y < 0.0 { self.y = screen_height as f64 }
         else if self.y > screen_height as f64 { self.y = 0.0 }
+        self.prev_x += self.x - x;
+        self.prev_y += self.y - y;
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::input::ScriptedController;
+
+    #[test]
+    fn crossing_threshold_awards_a_life() {
+        let mut player = Player::new(400.0, 300.0);
+        player.add_score(9_990);
+        assert_eq!(player.lives(), 3);
+
+        player.add_score(20);
+
+        assert_eq!(player.lives(), 4);
+    }
+
+    #[test]
+    fn bonus_lives_are_capped() {
+        let mut player = Player::new(400.0, 300.0);
+        player.bonus_life = BonusLifeRule { every: 100, max_lives: 5 };
+
+        player.add_score(1_000);
+
+        assert_eq!(player.lives(), 5);
+    }
+
+    #[test]
+    fn shield_drains_while_held_and_recharges_after() {
+        let mut player = Player::new(400.0, 300.0);
+        let shield = ScriptedController::new(vec![Command::Shield]);
+        let idle = ScriptedController::new(vec![]);
+
+        player.update(&shield, 0.5, 800, 600);
+        assert!(player.is_shielded());
+        assert!(player.is_invulnerable());
+        assert!((player.shield_energy - 0.75).abs() < 1e-9);
+
+        player.update(&shield, 2.0, 800, 600);
+        player.update(&shield, 0.1, 800, 600);
+        assert!(!player.is_shielded());
+        assert_eq!(player.shield_energy, 0.0);
+
+        player.update(&idle, 1.0, 800, 600);
+        assert!((player.shield_energy - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn thrusting_only_while_accelerating() {
+        let mut player = Player::new(400.0, 300.0);
+
+        player.update(&ScriptedController::new(vec![Command::Accelerate]), 0.1, 800, 600);
+        assert!(player.thrusting);
+
+        player.update(&ScriptedController::new(vec![]), 0.1, 800, 600);
+        assert!(!player.thrusting);
+    }
+
+    #[test]
+    fn shield_blocks_hits() {
+        let mut player = Player::new(400.0, 300.0);
+        player.update(&ScriptedController::new(vec![Command::Shield]), 0.1, 800, 600);
+
+        player.hit(800, 600);
+
+        assert_eq!(player.lives(), 3);
+    }
+
+    #[test]
+    fn score_saturates_instead_of_overflowing() {
+        let mut player = Player::new(400.0, 300.0);
+        player.score = u32::MAX - 10;
+
+        player.add_score(100);
+
+        assert_eq!(player.score, u32::MAX);
+    }
+
+    #[test]
+    fn no_bonus_when_already_at_cap() {
+        let mut player = Player::new(400.0, 300.0);
+        player.bonus_life = BonusLifeRule { every: 100, max_lives: 3 };
+
+        player.add_score(100);
+
+        assert_eq!(player.lives(), 3);
+        assert!(player.bonus_life_timer.is_none());
+    }
+}
@@ -0,0 +1,222 @@
+use std::{f64::consts::PI, time::Duration};
+
+use rand::Rng;
+
+use crate::{core::{colour::RGB, renderer::{Point, Renderer}}, geometry::{Polygon, Vec2}, laser::{Faction, Laser}, utils::{self, triangle_polygon_collision}};
+
+const ZIG_ZAG_TURN_RANGE: std::ops::Range<f64> = 0.8..1.6;
+const AIM_JITTER: f64 = 0.15;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SaucerSize {
+    Large,
+    Small,
+}
+
+impl SaucerSize {
+    pub fn scale(self) -> f64 {
+        match self {
+            SaucerSize::Large => 24.0,
+            SaucerSize::Small => 14.0,
+        }
+    }
+
+    pub fn speed(self) -> f64 {
+        match self {
+            SaucerSize::Large => 100.0,
+            SaucerSize::Small => 150.0,
+        }
+    }
+
+    pub fn firing_interval(self) -> Duration {
+        match self {
+            SaucerSize::Large => Duration::from_millis(1500),
+            SaucerSize::Small => Duration::from_millis(1000),
+        }
+    }
+
+    pub fn is_aimed(self) -> bool {
+        self == SaucerSize::Small
+    }
+}
+
+pub struct Saucer {
+    pub size: SaucerSize,
+    x: f64,
+    y: f64,
+    prev_x: f64,
+    prev_y: f64,
+    velocity_x: f64,
+    velocity_y: f64,
+    time_until_turn: Duration,
+    time_since_fired: Duration,
+    vertices: Polygon,
+}
+
+impl Saucer {
+    pub fn new(size: SaucerSize, x: f64, y: f64, velocity_x: f64) -> Self {
+        let mut saucer = Saucer {
+            size,
+            x,
+            y,
+            prev_x: x,
+            prev_y: y,
+            velocity_x,
+            velocity_y: 0.0,
+            time_until_turn: Duration::from_secs(1),
+            time_since_fired: Duration::ZERO,
+            vertices: Polygon::default(),
+        };
+        saucer.recalculate_vertices();
+        saucer
+    }
+
+    pub fn spawn(rng: &mut impl Rng, size: SaucerSize, screen_width: u32, screen_height: u32) -> Self {
+        let margin = size.scale();
+        let y = rng.gen_range(0.15..0.85) * screen_height as f64;
+        if rng.gen_bool(0.5) {
+            Saucer::new(size, -margin, y, size.speed())
+        } else {
+            Saucer::new(size, screen_width as f64 + margin, y, -size.speed())
+        }
+    }
+
+    pub fn update(&mut self, rng: &mut impl Rng, dt: f64, screen_height: u32) {
+        self.prev_x = self.x;
+        self.prev_y = self.y;
+
+        let dt_duration = Duration::from_secs_f64(dt);
+        self.time_since_fired = self.time_since_fired.saturating_add(dt_duration);
+        if self.time_until_turn <= dt_duration {
+            self.zig_zag(rng);
+        } else {
+            self.time_until_turn -= dt_duration;
+        }
+
+        self.x += self.velocity_x * dt;
+        self.y += self.velocity_y * dt;
+        self.ensure_saucer_is_on_screen(screen_height);
+        self.recalculate_vertices();
+    }
+
+    pub fn fire(&mut self, rng: &mut impl Rng, target: Vec2) -> Option<Laser> {
+        if self.time_since_fired < self.size.firing_interval() {
+            return None;
+        }
+        self.time_since_fired = Duration::ZERO;
+
+        let angle = if self.size.is_aimed() {
+            (target.y - self.y).atan2(target.x - self.x) + rng.gen_range(-AIM_JITTER..AIM_JITTER)
+        } else {
+            rng.gen_range(0.0..2.0 * PI)
+        };
+        Some(Laser::new(self.x, self.y, angle, Faction::Enemy))
+    }
+
+    pub fn has_left_screen(&self, screen_width: u32) -> bool {
+        let margin = 2.0 * self.size.scale();
+        (self.velocity_x > 0.0 && self.x > screen_width as f64 + margin)
+            || (self.velocity_x < 0.0 && self.x < -margin)
+    }
+
+    pub fn draw(&self, renderer: &mut impl Renderer, colour: RGB, alpha: f64) -> Result<(), String> {
+        let x = utils::lerp(self.prev_x, self.x, alpha);
+        let y = utils::lerp(self.prev_y, self.y, alpha);
+        let outline = self.vertices.translated(Vec2::new(x - self.x, y - self.y));
+        renderer.draw_lines(&outline.to_closed_points(), colour)?;
+
+        let scale = self.size.scale();
+        let rim = [Point::from(Vec2::new(x - scale, y)), Point::from(Vec2::new(x + scale, y))];
+        renderer.draw_lines(&rim, colour)?;
+        let dome = [
+            Point::from(Vec2::new(x - 0.4 * scale, y - 0.3 * scale)),
+            Point::from(Vec2::new(x + 0.4 * scale, y - 0.3 * scale)),
+        ];
+        renderer.draw_lines(&dome, colour)
+    }
+
+    pub fn is_hit(&self, start: Vec2, end: Vec2) -> bool {
+        utils::segment_polygon_collision(start, end, &self.vertices)
+    }
+
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        self.vertices.bounds()
+    }
+
+    pub fn is_colliding(&self, polygon: &Polygon) -> bool {
+        triangle_polygon_collision(polygon, &self.vertices)
+    }
+
+    pub fn vertices(&self) -> &Polygon {
+        &self.vertices
+    }
+
+    pub fn position(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    fn zig_zag(&mut self, rng: &mut impl Rng) {
+        let directions = [-1.0, 0.0, 1.0];
+        let direction = directions[rng.gen_range(0..directions.len())];
+        self.velocity_y = direction * 0.6 * self.size.speed();
+        self.time_until_turn = Duration::from_secs_f64(rng.gen_range(ZIG_ZAG_TURN_RANGE));
+    }
+
+    fn ensure_saucer_is_on_screen(&mut self, screen_height: u32) {
+        let y = self.y;
+        if self.y < 0.0 { self.y = screen_height as f64 }
+        else if self.y > screen_height as f64 { self.y = 0.0 }
+        self.prev_y += self.y - y;
+    }
+
+    fn recalculate_vertices(&mut self) {
+        let outline = [
+            (-1.0, 0.0), (-0.4, -0.3), (-0.25, -0.6), (0.25, -0.6),
+            (0.4, -0.3), (1.0, 0.0), (0.4, 0.35), (-0.4, 0.35),
+        ];
+        let scale = self.size.scale();
+        self.vertices = Polygon::new(
+            outline.iter().map(|&(x, y)| Vec2::new(self.x + x * scale, self.y + y * scale)).collect(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn saucer_changes_vertical_direction() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut saucer = Saucer::new(SaucerSize::Large, 0.0, 300.0, 100.0);
+        let mut headings = Vec::new();
+        for _ in 0..1200 {
+            saucer.update(&mut rng, 1.0 / 120.0, 600);
+            headings.push((saucer.velocity_y / 60.0).round() as i32);
+        }
+        headings.dedup();
+        assert!(headings.len() > 2);
+    }
+
+    #[test]
+    fn small_saucer_aims_at_target() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut saucer = Saucer::new(SaucerSize::Small, 100.0, 300.0, 150.0);
+        saucer.time_since_fired = Duration::MAX;
+
+        let laser = saucer.fire(&mut rng, Vec2::new(500.0, 300.0)).unwrap();
+
+        assert!(laser.angle.abs() <= AIM_JITTER);
+        assert_eq!(laser.owner, Faction::Enemy);
+        assert!(saucer.fire(&mut rng, Vec2::new(500.0, 300.0)).is_none());
+    }
+
+    #[test]
+    fn saucer_leaves_past_far_edge() {
+        let saucer = Saucer::new(SaucerSize::Large, 850.0, 300.0, 100.0);
+        assert!(saucer.has_left_screen(800));
+        let saucer = Saucer::new(SaucerSize::Large, 850.0, 300.0, -100.0);
+        assert!(!saucer.has_left_screen(800));
+    }
+}
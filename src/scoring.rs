@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use crate::{asteroid::AsteroidSize, saucer::SaucerSize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    Asteroid(AsteroidSize),
+    Saucer(SaucerSize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoreTable {
+    pub large_asteroid: u32,
+    pub medium_asteroid: u32,
+    pub small_asteroid: u32,
+    pub large_saucer: u32,
+    pub small_saucer: u32,
+    pub streak_window: Duration,
+    pub hits_per_multiplier: u32,
+    pub max_multiplier: u32,
+}
+
+impl Default for ScoreTable {
+    fn default() -> Self {
+        ScoreTable {
+            large_asteroid: 20,
+            medium_asteroid: 50,
+            small_asteroid: 100,
+            large_saucer: 200,
+            small_saucer: 1000,
+            streak_window: Duration::from_secs(2),
+            hits_per_multiplier: 5,
+            max_multiplier: 4,
+        }
+    }
+}
+
+impl ScoreTable {
+    pub fn points(&self, target: Target) -> u32 {
+        match target {
+            Target::Asteroid(AsteroidSize::Large) => self.large_asteroid,
+            Target::Asteroid(AsteroidSize::Medium) => self.medium_asteroid,
+            Target::Asteroid(AsteroidSize::Small) => self.small_asteroid,
+            Target::Saucer(SaucerSize::Large) => self.large_saucer,
+            Target::Saucer(SaucerSize::Small) => self.small_saucer,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BonusLifeRule {
+    pub every: u32,
+    pub max_lives: u8,
+}
+
+impl Default for BonusLifeRule {
+    fn default() -> Self {
+        BonusLifeRule { every: 10_000, max_lives: 9 }
+    }
+}
+
+impl BonusLifeRule {
+    pub fn lives_earned(&self, old_score: u32, new_score: u32) -> u32 {
+        if self.every == 0 {
+            return 0;
+        }
+        new_score / self.every - old_score / self.every
+    }
+}
+
+pub struct Scoring {
+    pub table: ScoreTable,
+    streak: u32,
+    time_since_hit: Duration,
+}
+
+impl Scoring {
+    pub fn new(table: ScoreTable) -> Self {
+        Scoring { table, streak: 0, time_since_hit: Duration::ZERO }
+    }
+
+    pub fn update(&mut self, dt: f64) {
+        self.time_since_hit += Duration::from_secs_f64(dt);
+        if self.time_since_hit > self.table.streak_window {
+            self.streak = 0;
+        }
+    }
+
+    pub fn award(&mut self, target: Target) -> u32 {
+        let points = self.table.points(target) * self.multiplier();
+        self.streak += 1;
+        self.time_since_hit = Duration::ZERO;
+        points
+    }
+
+    pub fn multiplier(&self) -> u32 {
+        let step = self.table.hits_per_multiplier.max(1);
+        (1 + self.streak / step).min(self.table.max_multiplier.max(1))
+    }
+
+    pub fn reset(&mut self) {
+        self.streak = 0;
+        self.time_since_hit = Duration::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smaller_asteroids_are_worth_more() {
+        let table = ScoreTable::default();
+        assert_eq!(table.points(Target::Asteroid(AsteroidSize::Large)), 20);
+        assert_eq!(table.points(Target::Asteroid(AsteroidSize::Medium)), 50);
+        assert_eq!(table.points(Target::Asteroid(AsteroidSize::Small)), 100);
+    }
+
+    #[test]
+    fn streak_raises_multiplier_up_to_cap() {
+        let mut scoring = Scoring::new(ScoreTable::default());
+        let awarded: Vec<u32> = (0..25)
+            .map(|_| scoring.award(Target::Asteroid(AsteroidSize::Large)))
+            .collect();
+
+        assert_eq!(awarded[0], 20);
+        assert_eq!(awarded[4], 20);
+        assert_eq!(awarded[5], 40);
+        assert_eq!(awarded[10], 60);
+        assert_eq!(awarded[15], 80);
+        assert_eq!(awarded[24], 80);
+    }
+
+    #[test]
+    fn streak_expires_after_window() {
+        let mut scoring = Scoring::new(ScoreTable::default());
+        for _ in 0..5 {
+            scoring.award(Target::Asteroid(AsteroidSize::Small));
+        }
+        assert_eq!(scoring.multiplier(), 2);
+
+        scoring.update(2.5);
+
+        assert_eq!(scoring.multiplier(), 1);
+    }
+
+    #[test]
+    fn bonus_lives_are_earned_per_threshold_crossed() {
+        let rule = BonusLifeRule::default();
+        assert_eq!(rule.lives_earned(0, 9_999), 0);
+        assert_eq!(rule.lives_earned(9_990, 10_010), 1);
+        assert_eq!(rule.lives_earned(10_010, 10_500), 0);
+        assert_eq!(rule.lives_earned(9_000, 31_000), 3);
+        assert_eq!(BonusLifeRule { every: 0, max_lives: 9 }.lives_earned(0, 50_000), 0);
+    }
+
+    #[test]
+    fn custom_table_is_used() {
+        let table = ScoreTable { small_asteroid: 7, max_multiplier: 1, ..ScoreTable::default() };
+        let mut scoring = Scoring::new(table);
+        for _ in 0..10 {
+            assert_eq!(scoring.award(Target::Asteroid(AsteroidSize::Small)), 7);
+        }
+    }
+}
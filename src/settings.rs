@@ -0,0 +1,11 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ShipStyle {
+    #[default]
+    Filled,
+    Outline,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Settings {
+    pub ship_style: ShipStyle,
+}
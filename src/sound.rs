@@ -0,0 +1,43 @@
+/// Cues raised by the simulation for a front end to play.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sound {
+    BonusLife,
+}
+
+impl Sound {
+    /// Mono samples in -1..1, synthesised so no audio assets are needed.
+    pub fn samples(self, sample_rate: u32) -> Vec<f32> {
+        match self {
+            Sound::BonusLife => chime(&[880.0, 1174.7, 1760.0], 0.08, sample_rate),
+        }
+    }
+}
+
+fn chime(notes: &[f64], note_length: f64, sample_rate: u32) -> Vec<f32> {
+    let per_note = (note_length * sample_rate as f64) as usize;
+    notes
+        .iter()
+        .flat_map(|&frequency| {
+            (0..per_note).map(move |i| {
+                let phase = (i as f64 * frequency / sample_rate as f64).fract();
+                let envelope = 1.0 - i as f64 / per_note as f64;
+                let square = if phase < 0.5 { 1.0 } else { -1.0 };
+                (0.25 * envelope * square) as f32
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bonus_life_chime_is_short_and_quiet() {
+        let samples = Sound::BonusLife.samples(44_100);
+
+        assert_eq!(samples.len(), 3 * 3_528);
+        assert!(samples.iter().all(|sample| sample.abs() <= 0.25));
+        assert!(samples.iter().any(|&sample| sample > 0.2));
+    }
+}
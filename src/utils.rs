@@ -1,10 +1,11 @@
 use rand::Rng;
 use std::f64::consts::PI;
 
-use crate::core::renderer::Point;
+use crate::geometry::{Polygon, Vec2};
 
-pub fn is_point_in_polygon(point: Point, vertices: &[Point]) -> bool {
-    if vertices.len() == 0 { return false }
+pub fn is_point_in_polygon(point: Vec2, polygon: &Polygon) -> bool {
+    let vertices = &polygon.vertices;
+    if vertices.is_empty() { return false }
     let mut is_inside = false;
     let mut j = vertices.len() - 1;
 
@@ -25,8 +26,7 @@ pub fn is_point_in_polygon(point: Point, vertices: &[Point]) -> bool {
     is_inside
 }
 
-pub fn generate_spawn_points(screen_width: u32, screen_height: u32, margin: f64) -> (f64, f64) {
-    let mut rng = rand::thread_rng();
+pub fn generate_spawn_points(rng: &mut impl Rng, screen_width: u32, screen_height: u32, margin: f64) -> (f64, f64) {
     let x: f64;
     let y: f64;
 
@@ -67,8 +67,11 @@ pub fn interpolate(y: i32, y1: i32, y2: i32, x1: i32, x2: i32) -> i32 {
     }
 }
 
-pub fn generate_velocity(min: f64, max: f64) -> (f64, f64) {
-    let mut rng = rand::thread_rng();
+pub fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+pub fn generate_velocity(rng: &mut impl Rng, min: f64, max: f64) -> (f64, f64) {
     let x_multiplier = if rng.gen::<bool>() { 1 } else { -1 };
     let velocity_x = rng.gen_range(min..max) * x_multiplier as f64;
     let y_multiplier = if rng.gen::<bool>() { 1 } else { -1 };
@@ -76,25 +79,40 @@ pub fn generate_velocity(min: f64, max: f64) -> (f64, f64) {
     (velocity_x, velocity_y)
 }
 
+pub fn generate_outline(rng: &mut impl Rng, scale: f64) -> Polygon {
+    let num_points = rng.gen_range(8..=12);
+    let step = 2.0 * PI / num_points as f64;
+
+    let vertices = (0..num_points)
+        .map(|i| {
+            let angle = step * (i as f64 + rng.gen_range(-0.3..0.3));
+            let radius = scale * rng.gen_range(0.6..1.0);
+            Vec2::from_angle(angle, radius)
+        })
+        .collect();
+
+    Polygon::new(vertices)
+}
+
 pub fn triangle_polygon_collision(
-    triangle: &Vec<Point>, 
-    polygon: &[Point],
+    triangle: &Polygon,
+    polygon: &Polygon,
 ) -> bool {
 
-    for &vertex in triangle {
+    for &vertex in &triangle.vertices {
         if is_point_in_polygon(vertex, polygon) {
             return true;
         }
     }
 
-    for i in 0..triangle.len() {
-        let p1 = triangle[i];
-        let p2 = triangle[(i + 1) % triangle.len()];
-
-        for j in 0..polygon.len() {
-            let q1 = polygon[j];
-            let q2 = polygon[(j + 1) % polygon.len()];
+    for &vertex in &polygon.vertices {
+        if is_point_in_polygon(vertex, triangle) {
+            return true;
+        }
+    }
 
+    for (p1, p2) in triangle.edges() {
+        for (q1, q2) in polygon.edges() {
             if lines_intersect(p1, p2, q1, q2) {
                 return true;
             }
@@ -104,30 +122,65 @@ pub fn triangle_polygon_collision(
     false
 }
 
-fn lines_intersect(
-    p1: Point, p2: Point,
-    q1: Point, q2: Point,
+pub fn segment_polygon_collision(start: Vec2, end: Vec2, polygon: &Polygon) -> bool {
+    if is_point_in_polygon(end, polygon) {
+        return true;
+    }
+
+    polygon.edges().any(|(q1, q2)| lines_intersect(start, end, q1, q2))
+}
+
+pub fn circle_polygon_collision(centre: Vec2, radius: f64, polygon: &Polygon) -> bool {
+    if is_point_in_polygon(centre, polygon) {
+        return true;
+    }
+
+    polygon.edges().any(|(q1, q2)| distance_to_segment(centre, q1, q2) <= radius)
+}
+
+fn distance_to_segment(point: Vec2, start: Vec2, end: Vec2) -> f64 {
+    let direction = end - start;
+    let length_squared = direction.dot(direction);
+    if length_squared == 0.0 {
+        return (point - start).length();
+    }
+    let t = ((point - start).dot(direction) / length_squared).clamp(0.0, 1.0);
+    (point - (start + direction * t)).length()
+}
+
+pub fn lines_intersect(
+    p1: Vec2, p2: Vec2,
+    q1: Vec2, q2: Vec2,
 ) -> bool {
-    fn orientation(p: Point, q: Point, r: Point) -> i32 {
+    fn orientation(p: Vec2, q: Vec2, r: Vec2) -> i32 {
         let val = (q.y - p.y) * (r.x - q.x) - (q.x - p.x) * (r.y - q.y);
-        if val == 0 {
-            0
-        } else if val > 0 {
+        if val > 0.0 {
             1
-        } else {
+        } else if val < 0.0 {
             -1
+        } else {
+            0
         }
     }
 
+    fn on_segment(p: Vec2, q: Vec2, r: Vec2) -> bool {
+        r.x >= p.x.min(q.x) && r.x <= p.x.max(q.x) && r.y >= p.y.min(q.y) && r.y <= p.y.max(q.y)
+    }
+
     let o1 = orientation(p1, p2, q1);
     let o2 = orientation(p1, p2, q2);
     let o3 = orientation(q1, q2, p1);
     let o4 = orientation(q1, q2, p2);
 
+    if o1 == 0 && o2 == 0 && o3 == 0 && o4 == 0 {
+        return on_segment(p1, p2, q1) || on_segment(p1, p2, q2)
+            || on_segment(q1, q2, p1) || on_segment(q1, q2, p2);
+    }
+
     o1 != o2 && o3 != o4
 }
 
-pub fn get_vertices(point: (f64, f64), angle: f64, scale: f64) -> Vec<Point>{
+pub fn get_vertices(point: (f64, f64), angle: f64, scale: f64) -> Polygon {
     let mut vertices = Vec::new();
     let (x, y) = point;
 
@@ -140,9 +193,283 @@ pub fn get_vertices(point: (f64, f64), angle: f64, scale: f64) -> Vec<Point>{
     let right_x = x + 0.5 * scale * ((angle - 2.0 * PI / 3.0).cos());
     let right_y = y + 0.5 * scale * ((angle - 2.0 * PI / 3.0).sin());
 
-    vertices.push(Point::new(tip_x as i32, tip_y as i32));
-    vertices.push(Point::new(left_x as i32, left_y as i32));
-    vertices.push(Point::new(right_x as i32, right_y as i32));
-    vertices
+    vertices.push(Vec2::new(tip_x, tip_y));
+    vertices.push(Vec2::new(left_x, left_y));
+    vertices.push(Vec2::new(right_x, right_y));
+    Polygon::new(vertices)
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rand::SeedableRng;
+
+    fn v(x: f64, y: f64) -> Vec2 {
+        Vec2::new(x, y)
+    }
+
+    fn polygon(points: &[(f64, f64)]) -> Polygon {
+        Polygon::new(points.iter().map(|&(x, y)| v(x, y)).collect())
+    }
+
+    fn square(x: f64, y: f64, size: f64) -> Polygon {
+        polygon(&[(x, y), (x + size, y), (x + size, y + size), (x, y + size)])
+    }
+
+    fn closed(mut polygon: Polygon) -> Polygon {
+        polygon.vertices.push(polygon.vertices[0]);
+        polygon
+    }
+
+    fn regular_polygon(x: f64, y: f64, radius: f64, sides: usize) -> Polygon {
+        Polygon::new(
+            (0..sides)
+                .map(|i| v(x, y) + Vec2::from_angle(2.0 * PI * i as f64 / sides as f64, radius))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn point_inside_square_is_detected() {
+        assert!(is_point_in_polygon(v(5.0, 5.0), &square(0.0, 0.0, 10.0)));
+    }
+
+    #[test]
+    fn point_outside_square_is_not_detected() {
+        let polygon = square(0.0, 0.0, 10.0);
+        assert!(!is_point_in_polygon(v(15.0, 5.0), &polygon));
+        assert!(!is_point_in_polygon(v(-1.0, 5.0), &polygon));
+        assert!(!is_point_in_polygon(v(5.0, 11.0), &polygon));
+        assert!(!is_point_in_polygon(v(5.0, -1.0), &polygon));
+    }
+
+    #[test]
+    fn empty_polygon_contains_nothing() {
+        assert!(!is_point_in_polygon(v(0.0, 0.0), &Polygon::default()));
+    }
+
+    #[test]
+    fn degenerate_polygons_contain_nothing() {
+        let single = polygon(&[(3.0, 3.0)]);
+        let segment = polygon(&[(0.0, 0.0), (10.0, 10.0)]);
+        let collinear = polygon(&[(0.0, 0.0), (5.0, 5.0), (10.0, 10.0)]);
+
+        assert!(!is_point_in_polygon(v(3.0, 3.0), &single));
+        assert!(!is_point_in_polygon(v(5.0, 5.0), &segment));
+        assert!(!is_point_in_polygon(v(5.0, 5.0), &collinear));
+    }
+
+    #[test]
+    fn wrapped_closing_vertex_does_not_change_result() {
+        let open = regular_polygon(100.0, 100.0, 40.0, 7);
+        let closed = closed(open.clone());
+
+        for &point in &[v(100.0, 100.0), v(130.0, 100.0), v(150.0, 100.0), v(100.0, 60.0)] {
+            assert_eq!(is_point_in_polygon(point, &open), is_point_in_polygon(point, &closed));
+        }
+    }
+
+    #[test]
+    fn point_in_concave_notch_is_outside() {
+        let polygon = polygon(&[(0.0, 0.0), (20.0, 0.0), (20.0, 20.0), (10.0, 5.0), (0.0, 20.0)]);
+        assert!(!is_point_in_polygon(v(10.0, 15.0), &polygon));
+        assert!(is_point_in_polygon(v(10.0, 2.0), &polygon));
+    }
+
+    #[test]
+    fn point_just_inside_slanted_edge_is_inside() {
+        let triangle = polygon(&[(0.0, 0.0), (3.0, 10.0), (0.0, 10.0)]);
+        assert!(is_point_in_polygon(v(0.25, 1.0), &triangle));
+        assert!(!is_point_in_polygon(v(0.35, 1.0), &triangle));
+    }
+
+    #[test]
+    fn segment_ending_inside_polygon_collides() {
+        assert!(segment_polygon_collision(v(-5.0, 5.0), v(5.0, 5.0), &square(0.0, 0.0, 10.0)));
+        assert!(segment_polygon_collision(v(5.0, 5.0), v(5.0, 5.0), &square(0.0, 0.0, 10.0)));
+    }
+
+    #[test]
+    fn segment_passing_through_polygon_collides() {
+        assert!(segment_polygon_collision(v(-50.0, 5.0), v(50.0, 5.0), &square(0.0, 0.0, 10.0)));
+    }
+
+    #[test]
+    fn segment_missing_polygon_does_not_collide() {
+        assert!(!segment_polygon_collision(v(-50.0, 15.0), v(50.0, 15.0), &square(0.0, 0.0, 10.0)));
+        assert!(!segment_polygon_collision(v(-50.0, 5.0), v(-1.0, 5.0), &square(0.0, 0.0, 10.0)));
+    }
+
+    #[test]
+    fn circle_touching_polygon_edge_collides() {
+        assert!(circle_polygon_collision(v(15.0, 5.0), 6.0, &square(0.0, 0.0, 10.0)));
+        assert!(circle_polygon_collision(v(5.0, 5.0), 1.0, &square(0.0, 0.0, 10.0)));
+        assert!(!circle_polygon_collision(v(15.0, 15.0), 6.0, &square(0.0, 0.0, 10.0)));
+    }
+
+    #[test]
+    fn outline_stays_within_scale() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        for _ in 0..100 {
+            let outline = generate_outline(&mut rng, 40.0);
+            assert!((8..=12).contains(&outline.vertices.len()));
+            assert!(outline.vertices.iter().all(|v| v.length() <= 40.0 && v.length() >= 24.0));
+            assert!(is_point_in_polygon(Vec2::ZERO, &outline));
+        }
+    }
+
+    #[test]
+    fn outline_is_reproducible_from_seed() {
+        let first = generate_outline(&mut rand::rngs::StdRng::seed_from_u64(3), 40.0);
+        let second = generate_outline(&mut rand::rngs::StdRng::seed_from_u64(3), 40.0);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn crossing_segments_intersect() {
+        assert!(lines_intersect(v(0.0, 0.0), v(10.0, 10.0), v(0.0, 10.0), v(10.0, 0.0)));
+    }
+
+    #[test]
+    fn parallel_segments_do_not_intersect() {
+        assert!(!lines_intersect(v(0.0, 0.0), v(10.0, 0.0), v(0.0, 5.0), v(10.0, 5.0)));
+    }
+
+    #[test]
+    fn separated_segments_do_not_intersect() {
+        assert!(!lines_intersect(v(0.0, 0.0), v(10.0, 10.0), v(20.0, 0.0), v(30.0, -10.0)));
+    }
+
+    #[test]
+    fn touching_endpoints_intersect() {
+        assert!(lines_intersect(v(0.0, 0.0), v(10.0, 0.0), v(10.0, 0.0), v(10.0, 10.0)));
+    }
+
+    #[test]
+    fn overlapping_collinear_segments_intersect() {
+        assert!(lines_intersect(v(0.0, 0.0), v(10.0, 0.0), v(5.0, 0.0), v(15.0, 0.0)));
+        assert!(lines_intersect(v(0.0, 0.0), v(10.0, 10.0), v(2.0, 2.0), v(4.0, 4.0)));
+    }
+
+    #[test]
+    fn disjoint_collinear_segments_do_not_intersect() {
+        assert!(!lines_intersect(v(0.0, 0.0), v(10.0, 0.0), v(11.0, 0.0), v(20.0, 0.0)));
+    }
+
+    #[test]
+    fn zero_length_segment_off_the_line_does_not_intersect() {
+        assert!(!lines_intersect(v(5.0, 4.0), v(5.0, 4.0), v(0.0, 0.0), v(10.0, 10.0)));
+    }
+
+    #[test]
+    fn ship_triangle_points_along_angle() {
+        let ship = get_vertices((100.0, 100.0), -PI / 2.0, 20.0);
+        let [tip, left, right] = ship.vertices[..] else { panic!("ship should be a triangle") };
+        assert!((tip - v(100.0, 80.0)).length() < 1e-9);
+        assert!(left.y > 100.0 && right.y > 100.0);
+        assert!((left.x - 100.0).signum() != (right.x - 100.0).signum());
+    }
+
+    #[test]
+    fn ship_overlapping_asteroid_edge_collides() {
+        let ship = get_vertices((100.0, 62.0), -PI / 2.0, 20.0);
+        let asteroid = closed(regular_polygon(100.0, 100.0, 40.0, 7));
+        assert!(triangle_polygon_collision(&ship, &asteroid));
+    }
+
+    #[test]
+    fn ship_inside_asteroid_collides() {
+        let ship = get_vertices((100.0, 100.0), 0.0, 20.0);
+        let asteroid = closed(regular_polygon(100.0, 100.0, 40.0, 7));
+        assert!(triangle_polygon_collision(&ship, &asteroid));
+    }
+
+    #[test]
+    fn asteroid_inside_ship_collides() {
+        let ship = get_vertices((100.0, 100.0), 0.0, 40.0);
+        let asteroid = closed(regular_polygon(102.0, 100.0, 3.0, 7));
+        assert!(triangle_polygon_collision(&ship, &asteroid));
+    }
+
+    #[test]
+    fn distant_ship_does_not_collide() {
+        let ship = get_vertices((300.0, 300.0), 0.0, 20.0);
+        let asteroid = closed(regular_polygon(100.0, 100.0, 40.0, 7));
+        assert!(!triangle_polygon_collision(&ship, &asteroid));
+    }
+
+    fn point() -> impl Strategy<Value = Vec2> {
+        (-1000..1000, -1000..1000).prop_map(|(x, y)| v(x as f64, y as f64))
+    }
+
+    proptest! {
+        #[test]
+        fn centre_of_regular_polygon_is_inside(
+            x in -500.0..500.0, y in -500.0..500.0, radius in 1.0..100.0, sides in 3usize..12,
+        ) {
+            let polygon = regular_polygon(x, y, radius, sides);
+            prop_assert!(is_point_in_polygon(v(x, y), &polygon));
+        }
+
+        #[test]
+        fn points_outside_bounding_box_are_outside(
+            x in -500.0..500.0, y in -500.0..500.0, radius in 1.0..100.0, sides in 3usize..12, offset in 101.0..1000.0,
+        ) {
+            let polygon = regular_polygon(x, y, radius, sides);
+            prop_assert!(!is_point_in_polygon(v(x + offset, y), &polygon));
+            prop_assert!(!is_point_in_polygon(v(x, y - offset), &polygon));
+        }
+
+        #[test]
+        fn closing_vertex_is_idempotent(
+            x in -500.0..500.0, y in -500.0..500.0, radius in 1.0..100.0, sides in 3usize..12, p in point(),
+        ) {
+            let open = regular_polygon(x, y, radius, sides);
+            prop_assert_eq!(is_point_in_polygon(p, &open), is_point_in_polygon(p, &closed(open.clone())));
+        }
+
+        #[test]
+        fn segment_through_centre_always_collides(
+            x in -500.0..500.0, y in -500.0..500.0, radius in 1.0..100.0, angle in -PI..PI, length in 0.0..1000.0,
+        ) {
+            let polygon = regular_polygon(x, y, radius, 7);
+            let offset = Vec2::from_angle(angle, radius + length);
+            prop_assert!(segment_polygon_collision(v(x, y) - offset, v(x, y) + offset, &polygon));
+        }
+
+        #[test]
+        fn ship_at_centre_of_any_outline_collides(seed in any::<u64>(), scale in 6.0..50.0, angle in -PI..PI) {
+            let outline = generate_outline(&mut rand::rngs::StdRng::seed_from_u64(seed), scale);
+            let ship = get_vertices((0.0, 0.0), angle, 20.0);
+            prop_assert!(triangle_polygon_collision(&ship, &outline));
+        }
+
+        #[test]
+        fn intersection_is_symmetric(p1 in point(), p2 in point(), q1 in point(), q2 in point()) {
+            let expected = lines_intersect(p1, p2, q1, q2);
+            prop_assert_eq!(expected, lines_intersect(q1, q2, p1, p2));
+            prop_assert_eq!(expected, lines_intersect(p2, p1, q1, q2));
+            prop_assert_eq!(expected, lines_intersect(p1, p2, q2, q1));
+        }
+
+        #[test]
+        fn ship_collides_with_asteroid_around_it(
+            x in 0.0..800.0, y in 0.0..600.0, angle in -PI..PI, radius in 2.0..60.0,
+        ) {
+            let ship = get_vertices((x, y), angle, 20.0);
+            let asteroid = closed(regular_polygon(x, y, radius, 7));
+            prop_assert!(triangle_polygon_collision(&ship, &asteroid));
+        }
+
+        #[test]
+        fn ship_never_collides_with_distant_asteroid(
+            x in 0.0..800.0, y in 0.0..600.0, angle in -PI..PI, radius in 1.0..60.0,
+        ) {
+            let ship = get_vertices((x, y), angle, 20.0);
+            let asteroid = closed(regular_polygon(x + 200.0, y, radius, 7));
+            prop_assert!(!triangle_polygon_collision(&ship, &asteroid));
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use rand::Rng;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DifficultyCurve {
+    pub base_asteroids: usize,
+    pub extra_asteroids_per_wave: usize,
+    pub max_asteroids: usize,
+    pub speed_step: f64,
+    pub max_speed_multiplier: f64,
+    pub intermission: Duration,
+    pub saucer_interval: std::ops::Range<f64>,
+    pub small_saucer_base_chance: f64,
+    pub small_saucer_chance_step: f64,
+}
+
+impl Default for DifficultyCurve {
+    fn default() -> Self {
+        DifficultyCurve {
+            base_asteroids: 4,
+            extra_asteroids_per_wave: 1,
+            max_asteroids: 11,
+            speed_step: 0.1,
+            max_speed_multiplier: 2.0,
+            intermission: Duration::from_secs(2),
+            saucer_interval: 10.0..20.0,
+            small_saucer_base_chance: 0.2,
+            small_saucer_chance_step: 0.1,
+        }
+    }
+}
+
+impl DifficultyCurve {
+    pub fn asteroid_count(&self, wave: u32) -> usize {
+        let extra = self.extra_asteroids_per_wave * wave.saturating_sub(1) as usize;
+        (self.base_asteroids + extra).min(self.max_asteroids)
+    }
+
+    pub fn speed_multiplier(&self, wave: u32) -> f64 {
+        (1.0 + self.speed_step * wave.saturating_sub(1) as f64).min(self.max_speed_multiplier)
+    }
+
+    pub fn small_saucer_chance(&self, wave: u32) -> f64 {
+        (self.small_saucer_base_chance + self.small_saucer_chance_step * wave.saturating_sub(1) as f64).clamp(0.0, 1.0)
+    }
+
+    /// An empty or reversed `saucer_interval` collapses to its start.
+    pub fn saucer_delay(&self, rng: &mut impl Rng) -> Duration {
+        let (start, end) = self.saucer_bounds();
+        Duration::from_secs_f64(rng.gen_range(start..=end))
+    }
+
+    /// The wait before a wave's first saucer: the shortest `saucer_delay`.
+    pub fn first_saucer_delay(&self) -> Duration {
+        Duration::from_secs_f64(self.saucer_bounds().0)
+    }
+
+    fn saucer_bounds(&self) -> (f64, f64) {
+        let start = self.saucer_interval.start.max(0.0);
+        (start, self.saucer_interval.end.max(start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn asteroid_count_grows_then_caps() {
+        let curve = DifficultyCurve::default();
+        assert_eq!(curve.asteroid_count(1), 4);
+        assert_eq!(curve.asteroid_count(2), 5);
+        assert_eq!(curve.asteroid_count(8), 11);
+        assert_eq!(curve.asteroid_count(50), 11);
+    }
+
+    #[test]
+    fn speed_multiplier_grows_then_caps() {
+        let curve = DifficultyCurve::default();
+        assert_eq!(curve.speed_multiplier(1), 1.0);
+        assert!((curve.speed_multiplier(3) - 1.2).abs() < 1e-9);
+        assert_eq!(curve.speed_multiplier(100), 2.0);
+    }
+
+    #[test]
+    fn small_saucers_become_more_likely() {
+        let curve = DifficultyCurve::default();
+        assert!((curve.small_saucer_chance(1) - 0.2).abs() < 1e-9);
+        assert!(curve.small_saucer_chance(4) > curve.small_saucer_chance(1));
+        assert_eq!(curve.small_saucer_chance(40), 1.0);
+    }
+
+    #[test]
+    fn saucer_delay_tolerates_empty_intervals() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut curve = DifficultyCurve::default();
+        for _ in 0..100 {
+            let delay = curve.saucer_delay(&mut rng).as_secs_f64();
+            assert!((10.0..=20.0).contains(&delay));
+        }
+
+        curve.saucer_interval = 5.0..5.0;
+        assert_eq!(curve.saucer_delay(&mut rng), Duration::from_secs(5));
+        curve.saucer_interval = 8.0..3.0;
+        assert_eq!(curve.saucer_delay(&mut rng), Duration::from_secs(8));
+        curve.saucer_interval = -4.0..-2.0;
+        assert_eq!(curve.saucer_delay(&mut rng), Duration::ZERO);
+        assert_eq!(curve.first_saucer_delay(), Duration::ZERO);
+    }
+}
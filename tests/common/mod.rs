@@ -0,0 +1,29 @@
+use std::cell::Cell;
+
+use asteroids::core::input::{Command, InputController};
+
+pub struct MockController {
+    commands: Vec<Command>,
+    polls: Cell<usize>,
+}
+
+impl MockController {
+    pub fn idle() -> Self {
+        MockController::holding(&[])
+    }
+
+    pub fn holding(commands: &[Command]) -> Self {
+        MockController { commands: commands.to_vec(), polls: Cell::new(0) }
+    }
+
+    pub fn polls(&self) -> usize {
+        self.polls.get()
+    }
+}
+
+impl InputController for MockController {
+    fn poll(&self) -> Vec<Command> {
+        self.polls.set(self.polls.get() + 1);
+        self.commands.clone()
+    }
+}
@@ -0,0 +1,499 @@
+mod common;
+
+use std::f64::consts::FRAC_PI_2;
+
+use asteroids::asteroid::{AsteroidConstructor, AsteroidSize};
+use asteroids::core::input::Command;
+use asteroids::game_state::{GameState, State, TIMESTEP};
+use asteroids::geometry::Vec2;
+use asteroids::laser::{Faction, Laser};
+use asteroids::particles::ParticleKind;
+use asteroids::saucer::{Saucer, SaucerSize};
+use asteroids::sound::Sound;
+use common::MockController;
+use rand::{rngs::StdRng, SeedableRng};
+
+const SCREEN_WIDTH: u32 = 800;
+const SCREEN_HEIGHT: u32 = 600;
+
+fn dt() -> f64 {
+    TIMESTEP.as_secs_f64()
+}
+
+fn empty_field() -> GameState {
+    GameState::new(SCREEN_WIDTH, SCREEN_HEIGHT, 0)
+}
+
+fn place_asteroid(game_state: &mut GameState, x: f64, y: f64, size: AsteroidSize) {
+    let asteroid = AsteroidConstructor::new(SCREEN_WIDTH, SCREEN_HEIGHT)
+        .size(size)
+        .x(x)
+        .y(y)
+        .velocity_x(0.0)
+        .velocity_y(0.0)
+        .build(&mut StdRng::seed_from_u64(0));
+    game_state.asteroids.push(asteroid);
+}
+
+#[test]
+fn controller_is_polled_every_tick() {
+    let mut game_state = empty_field();
+    let controller = MockController::idle();
+    game_state.update(&controller, dt());
+    assert!(controller.polls() > 0);
+}
+
+#[test]
+fn asteroid_on_spawn_costs_a_life() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 400.0, 300.0, AsteroidSize::Large);
+
+    game_state.update(&MockController::idle(), dt());
+    game_state.update(&MockController::idle(), dt());
+
+    assert_eq!(game_state.player.lives(), 2);
+}
+
+#[test]
+fn player_is_invulnerable_after_being_hit() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 400.0, 300.0, AsteroidSize::Large);
+
+    for _ in 0..120 {
+        game_state.update(&MockController::idle(), dt());
+    }
+
+    assert_eq!(game_state.player.lives(), 2);
+}
+
+#[test]
+fn game_ends_when_lives_run_out() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 400.0, 300.0, AsteroidSize::Large);
+
+    for _ in 0..(15.0 / dt()) as usize {
+        let controller = match game_state.player.is_respawning() {
+            true => MockController::holding(&[Command::Fire]),
+            false => MockController::idle(),
+        };
+        game_state.update(&controller, dt());
+        if game_state.state == State::GameOver {
+            break;
+        }
+    }
+
+    assert!(game_state.state == State::GameOver);
+    assert!(game_state.player.is_dead());
+}
+
+#[test]
+fn firing_at_asteroid_scores() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 400.0, 150.0, AsteroidSize::Small);
+
+    let controller = MockController::holding(&[Command::Fire]);
+    for _ in 0..(1.0 / dt()) as usize {
+        game_state.update(&controller, dt());
+    }
+
+    assert_eq!(game_state.player.score, 100);
+    assert!(game_state.asteroids.is_empty());
+}
+
+#[test]
+fn bonus_life_raises_a_sound_cue() {
+    let mut game_state = empty_field();
+    game_state.player.score = 9_950;
+    place_asteroid(&mut game_state, 400.0, 150.0, AsteroidSize::Small);
+
+    let controller = MockController::holding(&[Command::Fire]);
+    for _ in 0..(1.0 / dt()) as usize {
+        game_state.update(&controller, dt());
+    }
+
+    assert_eq!(game_state.player.lives(), 4);
+    assert_eq!(game_state.take_sounds(), vec![Sound::BonusLife]);
+    assert!(game_state.take_sounds().is_empty());
+}
+
+#[test]
+fn lasers_do_not_tunnel_through_small_asteroids_at_large_steps() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 400.0, 200.0, AsteroidSize::Small);
+
+    let step = 0.1;
+    game_state.update(&MockController::holding(&[Command::Fire]), step);
+    for _ in 0..10 {
+        game_state.update(&MockController::idle(), step);
+    }
+
+    assert_eq!(game_state.player.score, 100);
+}
+
+#[test]
+fn shooting_large_asteroid_splits_it_into_two_medium() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 400.0, 150.0, AsteroidSize::Large);
+
+    game_state.update(&MockController::holding(&[Command::Fire]), dt());
+    for _ in 0..(0.5 / dt()) as usize {
+        game_state.update(&MockController::idle(), dt());
+    }
+
+    let sizes: Vec<AsteroidSize> = game_state.asteroids.iter().map(|a| a.size).collect();
+    assert_eq!(sizes, vec![AsteroidSize::Medium, AsteroidSize::Medium]);
+    assert_eq!(game_state.player.score, 20);
+}
+
+#[test]
+fn first_wave_spawns_after_intermission() {
+    let mut game_state = empty_field();
+
+    game_state.update(&MockController::idle(), dt());
+    assert_eq!(game_state.wave, 1);
+    assert!(game_state.is_intermission());
+    assert!(game_state.asteroids.is_empty());
+
+    while game_state.is_intermission() {
+        game_state.update(&MockController::idle(), dt());
+    }
+
+    assert_eq!(game_state.asteroids.len(), game_state.difficulty.asteroid_count(1));
+}
+
+#[test]
+fn clearing_a_wave_starts_a_bigger_one() {
+    let mut game_state = empty_field();
+    while game_state.wave < 1 || game_state.is_intermission() {
+        game_state.update(&MockController::idle(), dt());
+    }
+    assert_eq!(game_state.asteroids.len(), game_state.difficulty.asteroid_count(1));
+
+    game_state.asteroids.clear();
+    game_state.update(&MockController::idle(), dt());
+    assert_eq!(game_state.wave, 2);
+    assert!(game_state.is_intermission());
+
+    while game_state.is_intermission() {
+        game_state.update(&MockController::idle(), dt());
+    }
+
+    assert_eq!(game_state.asteroids.len(), game_state.difficulty.asteroid_count(2));
+    assert!(game_state.difficulty.asteroid_count(2) > game_state.difficulty.asteroid_count(1));
+}
+
+#[test]
+fn ship_stays_hidden_until_spawn_area_is_clear() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 400.0, 300.0, AsteroidSize::Large);
+
+    for _ in 0..300 {
+        game_state.update(&MockController::idle(), dt());
+    }
+    assert!(game_state.player.is_respawning());
+
+    game_state.asteroids.clear();
+    game_state.update(&MockController::idle(), dt());
+
+    assert!(!game_state.player.is_respawning());
+    assert_eq!(game_state.player.lives(), 2);
+}
+
+#[test]
+fn pressing_fire_forces_respawn() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 400.0, 300.0, AsteroidSize::Large);
+
+    for _ in 0..10 {
+        game_state.update(&MockController::holding(&[Command::Fire]), dt());
+    }
+    assert!(game_state.player.is_respawning());
+
+    game_state.update(&MockController::idle(), dt());
+    game_state.update(&MockController::holding(&[Command::Fire]), dt());
+
+    assert!(!game_state.player.is_respawning());
+    assert!(game_state.player.is_invulnerable());
+}
+
+#[test]
+fn hyperspace_moves_player_then_cools_down() {
+    let mut game_state = empty_field();
+    game_state.hyperspace_failure_chance = 0.0;
+    let start = game_state.player.position();
+
+    game_state.update(&MockController::holding(&[Command::Hyperspace]), dt());
+    let destination = game_state.player.position();
+    assert_ne!(destination, start);
+
+    game_state.update(&MockController::holding(&[Command::Hyperspace]), dt());
+    assert_eq!(game_state.player.position(), destination);
+    assert_eq!(game_state.player.lives(), 3);
+}
+
+#[test]
+fn hyperspace_avoids_asteroids_when_possible() {
+    let mut game_state = empty_field();
+    game_state.hyperspace_failure_chance = 0.0;
+    place_asteroid(&mut game_state, 200.0, 150.0, AsteroidSize::Large);
+    place_asteroid(&mut game_state, 600.0, 450.0, AsteroidSize::Large);
+
+    game_state.update(&MockController::holding(&[Command::Hyperspace]), dt());
+
+    assert!(game_state.is_area_clear(game_state.player.position(), 80.0));
+}
+
+#[test]
+fn hyperspace_on_a_tiny_screen_lands_in_the_centre() {
+    let mut game_state = GameState::new(60, 200, 0);
+    game_state.hyperspace_failure_chance = 0.0;
+
+    game_state.update(&MockController::holding(&[Command::Hyperspace]), dt());
+
+    let destination = game_state.player.position();
+    assert_eq!(destination.x, 30.0);
+    assert!((40.0..=160.0).contains(&destination.y));
+}
+
+#[test]
+fn failed_hyperspace_costs_a_life_even_when_invulnerable() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 400.0, 300.0, AsteroidSize::Small);
+    game_state.update(&MockController::idle(), dt());
+    game_state.update(&MockController::idle(), dt());
+    assert_eq!(game_state.player.lives(), 2);
+    game_state.update(&MockController::holding(&[Command::Fire]), dt());
+    assert!(game_state.player.is_invulnerable());
+
+    game_state.hyperspace_failure_chance = 1.0;
+    game_state.update(&MockController::holding(&[Command::Hyperspace]), dt());
+
+    assert_eq!(game_state.player.lives(), 1);
+}
+
+#[test]
+fn failed_hyperspace_costs_a_life_with_the_shield_held() {
+    let mut game_state = empty_field();
+    game_state.hyperspace_failure_chance = 1.0;
+    game_state.update(&MockController::holding(&[Command::Shield]), dt());
+    assert!(game_state.player.is_shielded());
+
+    game_state.update(&MockController::holding(&[Command::Shield, Command::Hyperspace]), dt());
+
+    assert_eq!(game_state.player.lives(), 2);
+}
+
+#[test]
+fn asteroids_bounce_off_shield() {
+    let mut game_state = empty_field();
+    let asteroid = AsteroidConstructor::new(SCREEN_WIDTH, SCREEN_HEIGHT)
+        .size(AsteroidSize::Small)
+        .x(470.0)
+        .y(300.0)
+        .velocity_x(-100.0)
+        .velocity_y(0.0)
+        .build(&mut StdRng::seed_from_u64(0));
+    game_state.asteroids.push(asteroid);
+
+    for _ in 0..120 {
+        game_state.update(&MockController::holding(&[Command::Shield]), dt());
+    }
+
+    assert_eq!(game_state.player.lives(), 3);
+    let (min, _) = game_state.asteroids[0].bounds();
+    assert!(min.x > 400.0);
+}
+
+#[test]
+fn negative_saucer_interval_does_not_panic() {
+    let mut game_state = empty_field();
+    game_state.difficulty.saucer_interval = -5.0..-1.0;
+    game_state.reset(1);
+
+    for _ in 0..(5.0 / dt()) as usize {
+        game_state.update(&MockController::idle(), dt());
+    }
+
+    assert_eq!(game_state.saucers.len(), 1);
+}
+
+#[test]
+fn shooting_a_large_saucer_scores() {
+    let mut game_state = empty_field();
+    game_state.saucers.push(Saucer::new(SaucerSize::Large, 400.0, 150.0, 0.0));
+
+    game_state.update(&MockController::holding(&[Command::Fire]), dt());
+    for _ in 0..60 {
+        game_state.update(&MockController::idle(), dt());
+    }
+
+    assert!(game_state.saucers.is_empty());
+    assert_eq!(game_state.player.score, 200);
+}
+
+#[test]
+fn saucer_and_asteroid_destroy_each_other() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 200.0, 150.0, AsteroidSize::Large);
+    game_state.saucers.push(Saucer::new(SaucerSize::Small, 200.0, 150.0, 0.0));
+
+    game_state.update(&MockController::idle(), dt());
+
+    assert!(game_state.saucers.is_empty());
+    assert_eq!(game_state.asteroids.len(), 2);
+    assert!(game_state.asteroids.iter().all(|a| a.size == AsteroidSize::Medium));
+    assert_eq!(game_state.player.score, 0);
+}
+
+#[test]
+fn enemy_laser_damages_player() {
+    let mut game_state = empty_field();
+    game_state.lasers.push(Laser::new(400.0, 200.0, FRAC_PI_2, Faction::Enemy));
+
+    for _ in 0..60 {
+        game_state.update(&MockController::idle(), dt());
+    }
+
+    assert_eq!(game_state.player.lives(), 2);
+    assert!(game_state.lasers.is_empty());
+}
+
+#[test]
+fn enemy_laser_passes_through_shielded_player() {
+    let mut game_state = empty_field();
+    game_state.lasers.push(Laser::new(400.0, 200.0, FRAC_PI_2, Faction::Enemy));
+
+    for _ in 0..60 {
+        game_state.update(&MockController::holding(&[Command::Shield]), dt());
+    }
+
+    assert_eq!(game_state.player.lives(), 3);
+    assert_eq!(game_state.laser_count(Faction::Enemy), 1);
+    assert!(game_state.lasers[0].y > 300.0);
+}
+
+#[test]
+fn enemy_laser_breaks_asteroids_without_scoring() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 200.0, 150.0, AsteroidSize::Small);
+    game_state.lasers.push(Laser::new(200.0, 50.0, FRAC_PI_2, Faction::Enemy));
+
+    for _ in 0..60 {
+        game_state.update(&MockController::idle(), dt());
+    }
+
+    assert!(game_state.asteroids.is_empty());
+    assert_eq!(game_state.player.score, 0);
+}
+
+#[test]
+fn enemy_laser_passes_through_saucers() {
+    let mut game_state = empty_field();
+    game_state.saucers.push(Saucer::new(SaucerSize::Large, 200.0, 150.0, 0.0));
+    game_state.lasers.push(Laser::new(200.0, 50.0, FRAC_PI_2, Faction::Enemy));
+
+    for _ in 0..60 {
+        game_state.update(&MockController::idle(), dt());
+    }
+
+    assert_eq!(game_state.saucers.len(), 1);
+}
+
+#[test]
+fn laser_hits_asteroid_on_the_step_it_wraps() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 798.0, 300.0, AsteroidSize::Small);
+    game_state.lasers.push(Laser::new(785.0, 300.0, 0.0, Faction::Player));
+
+    game_state.update(&MockController::idle(), 0.1);
+
+    assert!(game_state.lasers.is_empty());
+    assert!(game_state.asteroids.is_empty());
+}
+
+#[test]
+fn laser_range_is_configurable() {
+    let mut game_state = empty_field();
+    game_state.laser_max_distance = 100.0;
+
+    game_state.update(&MockController::holding(&[Command::Fire]), dt());
+    assert_eq!(game_state.laser_count(Faction::Player), 1);
+    for _ in 0..40 {
+        game_state.update(&MockController::idle(), dt());
+    }
+
+    assert_eq!(game_state.laser_count(Faction::Player), 0);
+}
+
+#[test]
+fn lasers_wrap_around_the_screen() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 400.0, 550.0, AsteroidSize::Small);
+
+    game_state.update(&MockController::holding(&[Command::Fire]), dt());
+    for _ in 0..150 {
+        game_state.update(&MockController::idle(), dt());
+    }
+
+    assert_eq!(game_state.player.score, 100);
+}
+
+#[test]
+fn ship_death_leaves_debris() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 400.0, 300.0, AsteroidSize::Large);
+
+    game_state.update(&MockController::idle(), dt());
+
+    let debris = game_state.particles.iter().filter(|p| matches!(p.kind, ParticleKind::Debris { .. })).count();
+    assert_eq!(debris, 3);
+}
+
+#[test]
+fn shattered_asteroid_throws_sparks_and_dots() {
+    let mut game_state = empty_field();
+    place_asteroid(&mut game_state, 400.0, 150.0, AsteroidSize::Small);
+
+    game_state.update(&MockController::holding(&[Command::Fire]), dt());
+    while !game_state.asteroids.is_empty() {
+        game_state.update(&MockController::idle(), dt());
+    }
+
+    assert!(game_state.particles.iter().any(|p| p.kind == ParticleKind::Spark));
+    assert!(game_state.particles.iter().any(|p| p.kind == ParticleKind::Dot));
+
+    for _ in 0..(1.5 / dt()) as usize {
+        game_state.update(&MockController::idle(), dt());
+    }
+    assert!(game_state.particles.is_empty());
+}
+
+#[test]
+fn same_seed_and_input_give_same_game() {
+    let run = || {
+        let mut game_state = GameState::new(SCREEN_WIDTH, SCREEN_HEIGHT, 1234);
+        let controller = MockController::holding(&[Command::Fire, Command::RotateLeft]);
+        for _ in 0..(10.0 / dt()) as usize {
+            game_state.update(&controller, dt());
+        }
+        (game_state.player.score, game_state.player.lives(), game_state.asteroids.len())
+    };
+
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn particles_do_not_change_gameplay() {
+    let run = |extra_sparks: usize| {
+        let mut game_state = GameState::new(SCREEN_WIDTH, SCREEN_HEIGHT, 1234);
+        game_state.particles.spawn_sparks(Vec2::new(400.0, 300.0), extra_sparks);
+        let controller = MockController::holding(&[Command::Fire, Command::RotateLeft]);
+        for _ in 0..(10.0 / dt()) as usize {
+            game_state.update(&controller, dt());
+        }
+        let positions: Vec<Vec2> = game_state.asteroids.iter().map(|asteroid| asteroid.position()).collect();
+        (game_state.player.score, positions)
+    };
+
+    assert_eq!(run(0), run(50));
+}
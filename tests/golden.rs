@@ -0,0 +1,106 @@
+use asteroids::core::colour::RGB;
+use asteroids::core::framebuffer::FramebufferRenderer;
+use asteroids::core::input::{Command, DemoController, InputController, MenuCommand, ScriptedController};
+use asteroids::core::renderer::Renderer;
+use asteroids::game_state::{GameState, State, TIMESTEP};
+use asteroids::settings::ShipStyle;
+
+// FNV-1a over the raw pixel bytes. When a frame changes on purpose, the
+// failing test writes the new frame next to the build output for review
+// and prints the hash to paste in here.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+fn render(game_state: &GameState) -> FramebufferRenderer {
+    let mut renderer = FramebufferRenderer::new(800, 600);
+    renderer.set_colour(RGB::BLACK);
+    renderer.clear();
+    game_state.draw(&mut renderer, 1.0).unwrap();
+    renderer
+}
+
+fn assert_golden(name: &str, renderer: &FramebufferRenderer, expected: u64) {
+    let ppm = renderer.to_ppm();
+    let actual = fnv1a(&ppm);
+    if actual != expected {
+        let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("{}.ppm", name));
+        renderer.save_ppm(&path).unwrap();
+        panic!("{} frame changed: hash {:#018x}, written to {}", name, actual, path.display());
+    }
+}
+
+fn seeded_game() -> GameState {
+    let mut game_state = GameState::new(800, 600, 7);
+    for tick in 0..600 {
+        let commands = DemoController::new(tick).poll();
+        game_state.update(&ScriptedController::new(commands), TIMESTEP.as_secs_f64());
+    }
+    game_state
+}
+
+#[test]
+fn playing_frame_matches_golden() {
+    let renderer = render(&seeded_game());
+    assert_golden("playing", &renderer, 0x1cd5_54e5_c7e5_a827);
+}
+
+#[test]
+fn paused_frame_matches_golden() {
+    let mut game_state = seeded_game();
+    game_state.handle_menu(MenuCommand::Back);
+    assert_eq!(game_state.state, State::Paused);
+
+    let renderer = render(&game_state);
+
+    assert_golden("paused", &renderer, 0xdd10_1110_75a4_e187);
+}
+
+#[test]
+fn game_over_frame_matches_golden() {
+    let mut game_state = GameState::new(800, 600, 7);
+    game_state.player.score = 1230;
+    game_state.state = State::GameOver;
+
+    let renderer = render(&game_state);
+
+    // Top-left block of the 'G' in "GAME OVER" at (centre - 110, centre - 100).
+    assert_eq!(renderer.pixel(290 + 4, 200), Some(RGB::WHITE));
+    assert_eq!(renderer.pixel(290, 200), Some(RGB::BLACK));
+    assert_golden("game_over", &renderer, 0x53cd_1cab_aeae_4729);
+}
+
+fn ship_frame(style: ShipStyle, commands: Vec<Command>) -> FramebufferRenderer {
+    let mut game_state = GameState::new(800, 600, 0);
+    game_state.settings.ship_style = style;
+    game_state.update(&ScriptedController::new(commands), TIMESTEP.as_secs_f64());
+    render(&game_state)
+}
+
+#[test]
+fn filled_ship_matches_golden() {
+    let renderer = ship_frame(ShipStyle::Filled, vec![]);
+
+    assert_eq!(renderer.pixel(400, 290), Some(RGB::WHITE));
+    assert_eq!(renderer.pixel(400, 316), Some(RGB::BLACK));
+    assert_golden("filled_ship", &renderer, 0x6227_2ae0_4199_08f1);
+}
+
+#[test]
+fn outline_ship_matches_golden() {
+    let renderer = ship_frame(ShipStyle::Outline, vec![]);
+
+    // Hollow above the crossbar, which sits three quarters of the way to the base.
+    assert_eq!(renderer.pixel(400, 290), Some(RGB::BLACK));
+    assert_eq!(renderer.pixel(400, 298), Some(RGB::WHITE));
+    assert_golden("outline_ship", &renderer, 0x0c62_f74b_e16f_2142);
+}
+
+#[test]
+fn thrusting_ship_matches_golden() {
+    let renderer = ship_frame(ShipStyle::Filled, vec![Command::Accelerate]);
+
+    // Tip of the flame behind the ship.
+    assert_eq!(renderer.pixel(400, 316), Some(RGB::WHITE));
+    assert_golden("thrusting_ship", &renderer, 0x1575_632e_2829_24f1);
+}
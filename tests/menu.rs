@@ -0,0 +1,163 @@
+use asteroids::asteroid::{AsteroidConstructor, AsteroidSize};
+use asteroids::core::input::{Command, MenuCommand, ScriptedController};
+use asteroids::game_state::{GameState, State, TIMESTEP};
+use asteroids::highscores::{HighScore, HighScores};
+use asteroids::settings::ShipStyle;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn title_screen() -> GameState {
+    let mut game_state = GameState::new(800, 600, 0);
+    game_state.enter_title();
+    game_state
+}
+
+fn lose_game(game_state: &mut GameState, score: u32) {
+    game_state.player.score = score;
+    let asteroid = AsteroidConstructor::new(800, 600)
+        .size(AsteroidSize::Large)
+        .x(400.0)
+        .y(300.0)
+        .velocity_x(0.0)
+        .velocity_y(0.0)
+        .build(&mut StdRng::seed_from_u64(0));
+    game_state.asteroids.push(asteroid);
+
+    for _ in 0..(15.0 / TIMESTEP.as_secs_f64()) as usize {
+        let commands = match game_state.player.is_respawning() {
+            true => vec![Command::Fire],
+            false => Vec::new(),
+        };
+        game_state.update(&ScriptedController::new(commands), TIMESTEP.as_secs_f64());
+        if !game_state.is_simulating() {
+            return;
+        }
+    }
+    panic!("game did not end");
+}
+
+#[test]
+fn play_starts_a_game_with_the_requested_seed() {
+    let mut game_state = title_screen();
+    game_state.next_seed = Some(42);
+
+    game_state.handle_menu(MenuCommand::Select);
+
+    assert_eq!(game_state.state, State::Playing);
+    assert_eq!(game_state.seed, 42);
+    assert_eq!(game_state.player.score, 0);
+    assert_eq!(game_state.wave, 0);
+}
+
+#[test]
+fn attract_mode_plays_behind_the_title() {
+    let mut game_state = title_screen();
+    let mut saw_asteroids = false;
+
+    for _ in 0..(90.0 / TIMESTEP.as_secs_f64()) as usize {
+        game_state.update(&ScriptedController::new(Vec::new()), TIMESTEP.as_secs_f64());
+        saw_asteroids |= !game_state.asteroids.is_empty();
+    }
+
+    assert!(matches!(game_state.state, State::Title | State::HighScores));
+    assert!(saw_asteroids);
+}
+
+#[test]
+fn options_toggle_ship_style() {
+    let mut game_state = title_screen();
+
+    game_state.handle_menu(MenuCommand::Down);
+    game_state.handle_menu(MenuCommand::Select);
+    assert_eq!(game_state.state, State::Options);
+
+    game_state.handle_menu(MenuCommand::Right);
+    assert_eq!(game_state.settings.ship_style, ShipStyle::Outline);
+
+    game_state.handle_menu(MenuCommand::Back);
+    assert_eq!(game_state.state, State::Title);
+}
+
+#[test]
+fn pause_menu_can_resume_or_quit_to_title() {
+    let mut game_state = title_screen();
+    game_state.handle_menu(MenuCommand::Select);
+
+    game_state.handle_menu(MenuCommand::Back);
+    assert_eq!(game_state.state, State::Paused);
+    game_state.handle_menu(MenuCommand::Select);
+    assert_eq!(game_state.state, State::Playing);
+
+    game_state.handle_menu(MenuCommand::Back);
+    game_state.handle_menu(MenuCommand::Down);
+    game_state.handle_menu(MenuCommand::Select);
+    assert_eq!(game_state.state, State::Title);
+}
+
+#[test]
+fn high_scores_return_to_title() {
+    let mut game_state = title_screen();
+    game_state.handle_menu(MenuCommand::Up);
+    game_state.handle_menu(MenuCommand::Up);
+    game_state.handle_menu(MenuCommand::Select);
+    assert_eq!(game_state.state, State::HighScores);
+
+    game_state.handle_menu(MenuCommand::Back);
+
+    assert_eq!(game_state.state, State::Title);
+}
+
+#[test]
+fn qualifying_score_enters_initials() {
+    let mut game_state = title_screen();
+    game_state.next_seed = Some(9);
+    game_state.handle_menu(MenuCommand::Select);
+    lose_game(&mut game_state, 1500);
+    assert_eq!(game_state.state, State::EnterInitials);
+
+    game_state.handle_menu(MenuCommand::Down);
+    game_state.handle_menu(MenuCommand::Select);
+    game_state.handle_menu(MenuCommand::Up);
+    game_state.handle_menu(MenuCommand::Select);
+    game_state.handle_menu(MenuCommand::Right);
+    assert_eq!(game_state.state, State::EnterInitials);
+    game_state.handle_menu(MenuCommand::Select);
+
+    assert_eq!(game_state.state, State::HighScores);
+    let entry = &game_state.high_scores.entries()[0];
+    assert_eq!(entry.initials, "ZBA");
+    assert_eq!(entry.score, 1500);
+    assert_eq!(entry.seed, 9);
+    assert_eq!(entry.date.len(), "YYYY-MM-DD".len());
+}
+
+#[test]
+fn low_score_skips_initials() {
+    let mut game_state = title_screen();
+    let mut high_scores = HighScores::new();
+    for rank in 0..10 {
+        let date = "2026-10-18".to_string();
+        high_scores.insert(HighScore { initials: "AAA".to_string(), score: 5000 - rank, date, wave: 1, seed: 0 });
+    }
+    game_state.high_scores = high_scores;
+    game_state.handle_menu(MenuCommand::Select);
+
+    lose_game(&mut game_state, 100);
+
+    assert_eq!(game_state.state, State::GameOver);
+}
+
+#[test]
+fn title_alternates_with_high_scores_when_idle() {
+    let mut game_state = title_screen();
+    let idle = ScriptedController::new(Vec::new());
+
+    for _ in 0..(11.0 / TIMESTEP.as_secs_f64()) as usize {
+        game_state.update(&idle, TIMESTEP.as_secs_f64());
+    }
+    assert_eq!(game_state.state, State::HighScores);
+
+    for _ in 0..(10.0 / TIMESTEP.as_secs_f64()) as usize {
+        game_state.update(&idle, TIMESTEP.as_secs_f64());
+    }
+    assert_eq!(game_state.state, State::Title);
+}
@@ -0,0 +1,118 @@
+use std::process::Command as Process;
+
+use asteroids::core::input::{DemoController, InputController, MenuCommand, ScriptedController};
+use asteroids::core::replay::{self, Recording, ReplayController};
+use asteroids::game_state::{GameState, State, TIMESTEP};
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("asteroids-{}-{}", name, std::process::id()))
+}
+
+fn headless(args: &[&str]) -> std::process::Output {
+    Process::new(env!("CARGO_BIN_EXE_headless")).args(args).output().unwrap()
+}
+
+fn record_game(game_state: &mut GameState, seed: u64) -> Recording {
+    game_state.next_seed = Some(seed);
+    game_state.handle_menu(MenuCommand::Select);
+    record_current_game(game_state)
+}
+
+fn record_current_game(game_state: &mut GameState) -> Recording {
+    let mut recording = Recording::new(game_state.seed);
+    for tick in 0..120 * 30 {
+        let commands = DemoController::new(tick).poll();
+        recording.record(&commands);
+        game_state.update(&ScriptedController::new(commands), TIMESTEP.as_secs_f64());
+        if game_state.state != State::Playing {
+            break;
+        }
+    }
+    recording.final_score = Some(game_state.player.score);
+    recording
+}
+
+fn replayed_score(recording: Recording) -> u32 {
+    let mut controller = ReplayController::new(recording);
+    let mut replayed = GameState::new(800, 600, controller.seed());
+    while !controller.is_finished() && replayed.state == State::Playing {
+        replayed.update(&controller, TIMESTEP.as_secs_f64());
+        controller.advance();
+    }
+    replayed.player.score
+}
+
+#[test]
+fn recorded_game_replays_to_the_same_score() {
+    let mut game_state = GameState::new(800, 600, 0);
+    game_state.enter_title();
+    let recording = record_game(&mut game_state, 11);
+    assert!(game_state.player.score > 0);
+
+    let path = temp_path("round-trip.replay");
+    recording.save(&path).unwrap();
+    let loaded = Recording::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let expected = loaded.final_score;
+    assert_eq!(Some(replayed_score(loaded)), expected);
+}
+
+#[test]
+fn game_started_after_attract_mode_replays_to_the_same_score() {
+    let mut game_state = GameState::new(800, 600, 0);
+    game_state.enter_title();
+    for _ in 0..100 {
+        game_state.update(&ScriptedController::new(vec![]), TIMESTEP.as_secs_f64());
+    }
+    let recording = record_game(&mut game_state, 11);
+
+    let expected = recording.final_score;
+    assert_eq!(Some(replayed_score(recording)), expected);
+}
+
+#[test]
+fn game_recorded_after_a_reset_replays_to_the_same_score() {
+    let mut game_state = GameState::new(800, 600, 0);
+    game_state.enter_title();
+    record_game(&mut game_state, 11);
+    assert_ne!(game_state.state, State::Playing);
+
+    game_state.reset(12);
+    game_state.state = State::Playing;
+    let recording = record_current_game(&mut game_state);
+
+    let expected = recording.final_score;
+    assert_eq!(Some(replayed_score(recording)), expected);
+}
+
+#[test]
+fn headless_soak_records_every_game() {
+    let path = temp_path("soak.replay");
+    let soak = headless(&["4000", "--seed", "1", "--record", path.to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&soak.stdout);
+    let games: usize = stdout.lines().find_map(|line| line.strip_prefix("games played: ")).unwrap().parse().unwrap();
+    assert!(games >= 2);
+
+    for game in 1..=games {
+        let path = replay::numbered_path(&path, game);
+        let replayed = headless(&["--replay", path.to_str().unwrap()]);
+        std::fs::remove_file(&path).unwrap();
+        assert!(replayed.status.success(), "game {}: {}", game, String::from_utf8_lossy(&replayed.stderr));
+    }
+}
+
+#[test]
+fn headless_replay_checks_the_final_score() {
+    let path = temp_path("headless.replay");
+    let path = path.to_str().unwrap();
+
+    assert!(headless(&["1200", "--seed", "5", "--record", path]).status.success());
+    let replayed = headless(&["--replay", path]);
+    let diverged = headless(&["--replay", path, "--expect-score", "999999"]);
+    std::fs::remove_file(path).unwrap();
+
+    assert!(replayed.status.success());
+    assert!(!diverged.status.success());
+    assert!(String::from_utf8_lossy(&diverged.stderr).contains("replay diverged"));
+}